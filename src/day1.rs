@@ -1,80 +1,546 @@
-fn parse_calibration_value(line: String) -> Option<u32> {
-    let mut first_digit: Option<u32> = None;
-    let mut last_digit: u32 = 0;
-    for character in line.chars() {
-        if let Some(digit) = character.to_digit(10) {
-            match first_digit {
-                None => {
-                    first_digit = Some(digit);
-                    last_digit = digit;
-                }
-                Some(_) => last_digit = digit,
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Code points of the zero in the common Unicode decimal digit blocks, each
+/// followed by the digits one to nine.
+const UNICODE_ZEROS: [u32; 44] = [
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66,
+    0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E950, 0x1FBF0,
+];
+
+/// Value of a decimal digit from any of the scripts in `UNICODE_ZEROS`,
+/// including the ASCII digits.
+pub fn unicode_digit(character: char) -> Option<u32> {
+    if let Some(digit) = character.to_digit(10) {
+        return Some(digit);
+    }
+
+    let code = character as u32;
+    UNICODE_ZEROS
+        .iter()
+        .find(|zero| (**zero..**zero + 10).contains(&code))
+        .map(|zero| code - zero)
+}
+
+pub fn sum_digit_values(lines: Vec<String>, unicode_digits: bool) -> u64 {
+    let mut vocabulary = Vocabulary::plain_digits();
+    vocabulary.unicode_digits = unicode_digits;
+
+    calibrate(&lines, &vocabulary).sum()
+}
+
+/// Words that count as digits, matched anywhere in a line. Words
+/// may overlap, so `eightwo` has both an 8 and a 2, and when several words
+/// start at the same position the longest one is used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u32)>,
+    pub ignore_case: bool,
+    /// Read decimal digits from other scripts, such as `٣` or `３`, as the
+    /// plain digit of the same value.
+    pub unicode_digits: bool,
+}
+
+impl Vocabulary {
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Vocabulary {
+            words: words
+                .into_iter()
+                .map(|(word, value)| (word.to_string(), value))
+                .collect(),
+            ignore_case: false,
+            unicode_digits: false,
+        }
+    }
+
+    /// The vocabulary words together with the plain digits in `digits`.
+    fn with_plain_digits<'a>(
+        words: impl IntoIterator<Item = (&'a str, u32)>,
+        digits: std::ops::RangeInclusive<u32>,
+    ) -> Self {
+        let mut vocabulary = Vocabulary::new(words);
+        vocabulary
+            .words
+            .extend(digits.map(|value| (value.to_string(), value)));
+        vocabulary
+    }
+
+    /// The digits zero to nine, as read in part 1.
+    pub fn plain_digits() -> Self {
+        Vocabulary::with_plain_digits([], 0..=9)
+    }
+
+    pub fn english() -> Self {
+        Vocabulary::with_plain_digits(
+            [
+                ("one", 1),
+                ("two", 2),
+                ("three", 3),
+                ("four", 4),
+                ("five", 5),
+                ("six", 6),
+                ("seven", 7),
+                ("eight", 8),
+                ("nine", 9),
+            ],
+            1..=9,
+        )
+    }
+
+    /// English including `zero` and `0`.
+    pub fn english_with_zero() -> Self {
+        let mut vocabulary = Vocabulary::english();
+        vocabulary.words.push(("zero".to_string(), 0));
+        vocabulary.words.push(("0".to_string(), 0));
+        vocabulary
+    }
+
+    pub fn swedish() -> Self {
+        Vocabulary::with_plain_digits(
+            [
+                ("ett", 1),
+                ("två", 2),
+                ("tre", 3),
+                ("fyra", 4),
+                ("fem", 5),
+                ("sex", 6),
+                ("sju", 7),
+                ("åtta", 8),
+                ("nio", 9),
+            ],
+            1..=9,
+        )
+    }
+
+    pub fn german() -> Self {
+        Vocabulary::with_plain_digits(
+            [
+                ("eins", 1),
+                ("zwei", 2),
+                ("drei", 3),
+                ("vier", 4),
+                ("fünf", 5),
+                ("sechs", 6),
+                ("sieben", 7),
+                ("acht", 8),
+                ("neun", 9),
+            ],
+            1..=9,
+        )
+    }
+
+    pub fn ignoring_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    pub fn with_unicode_digits(mut self) -> Self {
+        self.unicode_digits = true;
+        self
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Vocabulary::english()
+    }
+}
+
+/// Either the name of a built in vocabulary or a custom table such as
+/// `zero=0,one=1,ten=10`.
+impl FromStr for Vocabulary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "english" => Ok(Vocabulary::english()),
+            "english-zero" => Ok(Vocabulary::english_with_zero()),
+            "swedish" => Ok(Vocabulary::swedish()),
+            "german" => Ok(Vocabulary::german()),
+            table if table.contains('=') => Ok(Vocabulary {
+                words: table
+                    .split(',')
+                    .map(|entry| {
+                        let (word, value) = entry.split_once('=').ok_or(Error::InvalidInput(
+                            format!("Missing '=' in vocabulary entry {}", entry),
+                        ))?;
+                        let word = word.trim();
+                        if word.is_empty() {
+                            return Err(Error::InvalidInput(format!(
+                                "Missing word in vocabulary entry {}",
+                                entry
+                            )));
+                        }
+                        let value = value.trim().parse().map_err(|err| {
+                            Error::InvalidInput(format!(
+                                "Invalid value in vocabulary entry {}: {}",
+                                entry, err
+                            ))
+                        })?;
+                        Ok((word.to_string(), value))
+                    })
+                    .collect::<Result<_, _>>()?,
+                ignore_case: false,
+                unicode_digits: false,
+            }),
+            name => Err(Error::InvalidInput(format!(
+                "{} is not a known vocabulary",
+                name
+            ))),
+        }
+    }
+}
+
+#[derive(Default)]
+struct AutomatonNode {
+    children: Vec<(char, usize)>,
+    fail: usize,
+    /// `(length, value)` of every word ending here, own word and those
+    /// reached through failure links alike.
+    outputs: Vec<(usize, u32)>,
+}
+
+/// Aho-Corasick automaton: a trie over the words whose failure links lead to
+/// the longest proper suffix that is also in the trie, so a whole text is
+/// matched in one pass without restarting at each position.
+struct Automaton {
+    nodes: Vec<AutomatonNode>,
+}
+
+impl Automaton {
+    fn new<I: IntoIterator<Item = (Vec<char>, u32)>>(words: I) -> Self {
+        let mut nodes = vec![AutomatonNode::default()];
+
+        for (word, value) in words {
+            let mut current = 0;
+            for character in word.iter() {
+                current = match Self::child(&nodes, current, *character) {
+                    Some(next) => next,
+                    None => {
+                        nodes.push(AutomatonNode::default());
+                        let next = nodes.len() - 1;
+                        nodes[current].children.push((*character, next));
+                        next
+                    }
+                };
+            }
+            if current != 0 && nodes[current].outputs.is_empty() {
+                nodes[current].outputs.push((word.len(), value));
+            }
+        }
+
+        // Breadth first, so the failure target of a node is complete before
+        // the node itself is visited.
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(current) = queue.pop_front() {
+            for (character, next) in nodes[current].children.clone() {
+                let fail = match current {
+                    0 => 0,
+                    _ => {
+                        let mut fail = nodes[current].fail;
+                        loop {
+                            if let Some(target) = Self::child(&nodes, fail, character) {
+                                break target;
+                            }
+                            if fail == 0 {
+                                break 0;
+                            }
+                            fail = nodes[fail].fail;
+                        }
+                    }
+                };
+                nodes[next].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[next].outputs.extend(inherited);
+                queue.push_back(next);
             }
         }
+
+        Automaton { nodes }
+    }
+
+    fn child(nodes: &[AutomatonNode], current: usize, character: char) -> Option<usize> {
+        nodes[current]
+            .children
+            .iter()
+            .find(|(it, _)| *it == character)
+            .map(|(_, next)| *next)
     }
-    first_digit.map(|it| it * 10 + last_digit)
-}
-
-fn sum_values_part_1(lines: Vec<String>) -> u32 {
-    return lines.into_iter().filter_map(parse_calibration_value).sum();
-}
-
-struct Digit(&'static str, u32);
-
-fn get_digit(index: usize, line: &String) -> Option<u32> {
-    let digits = vec![
-        Digit("one", 1),
-        Digit("two", 2),
-        Digit("three", 3),
-        Digit("four", 4),
-        Digit("five", 5),
-        Digit("six", 6),
-        Digit("seven", 7),
-        Digit("eight", 8),
-        Digit("nine", 9),
-    ];
-    let current = line.split_at(index).1;
-    for Digit(spelled, value) in digits {
-        if current.starts_with(spelled) {
-            return Some(value);
-        } else if current.starts_with(char::from_u32(value + 48).unwrap()) {
-            return Some(value);
+
+    fn step(&self, mut current: usize, character: char) -> usize {
+        loop {
+            if let Some(next) = Self::child(&self.nodes, current, character) {
+                return next;
+            }
+            if current == 0 {
+                return 0;
+            }
+            current = self.nodes[current].fail;
         }
     }
-    None
 }
 
-fn parse_calibration_value_advanced(line: String) -> Option<u32> {
-    let current_line = line.clone();
-    let mut first_digit: Option<u32> = None;
-    let mut last_digit: u32 = 0;
+/// One normalized character of a line. Case folding can turn a character into
+/// several, and a word only matches on whole characters of the line.
+struct Unit {
+    character: char,
+    /// Byte index of the line character this came from.
+    origin: usize,
+    starts: bool,
+    ends: bool,
+}
+
+/// Digit vocabulary compiled into one automaton over the words for the forward
+/// scan and one over the reversed words for the backward scan, so finding the
+/// first or last digit reads each character of the line at most once.
+struct DigitMatcher {
+    forward: Automaton,
+    backward: Automaton,
+    longest: usize,
+    ignore_case: bool,
+    unicode_digits: bool,
+}
+
+impl DigitMatcher {
+    fn new(vocabulary: &Vocabulary) -> Self {
+        let words: Vec<(Vec<char>, u32)> = vocabulary
+            .words
+            .iter()
+            .map(|(word, value)| {
+                let word = match vocabulary.ignore_case {
+                    true => word.to_lowercase(),
+                    false => word.clone(),
+                };
+                (word.chars().collect(), *value)
+            })
+            .collect();
+
+        DigitMatcher {
+            forward: Automaton::new(words.iter().cloned()),
+            backward: Automaton::new(
+                words
+                    .iter()
+                    .map(|(word, value)| (word.iter().rev().copied().collect(), *value)),
+            ),
+            longest: words.iter().map(|(word, _)| word.len()).max().unwrap_or(0),
+            ignore_case: vocabulary.ignore_case,
+            unicode_digits: vocabulary.unicode_digits,
+        }
+    }
+
+    fn normalize(&self, line: &str) -> Vec<Unit> {
+        let mut units = Vec::with_capacity(line.len());
+
+        for (origin, character) in line.char_indices() {
+            let normalized = match self.unicode_digits {
+                true => unicode_digit(character)
+                    .and_then(|digit| char::from_digit(digit, 10))
+                    .unwrap_or(character),
+                false => character,
+            };
+            let start = units.len();
+            match self.ignore_case {
+                true => units.extend(normalized.to_lowercase().map(|lower| Unit {
+                    character: lower,
+                    origin,
+                    starts: false,
+                    ends: false,
+                })),
+                false => units.push(Unit {
+                    character: normalized,
+                    origin,
+                    starts: false,
+                    ends: false,
+                }),
+            }
+            units[start].starts = true;
+            units.last_mut().unwrap().ends = true;
+        }
+
+        units
+    }
+
+    /// The match covering `units[start..end]`, if it lines up with whole
+    /// characters of `line`.
+    fn digit_match(
+        line: &str,
+        units: &[Unit],
+        start: usize,
+        end: usize,
+        value: u32,
+    ) -> Option<DigitMatch> {
+        if !units[start].starts || !units[end - 1].ends {
+            return None;
+        }
+
+        let from = units[start].origin;
+        let to = units[end - 1].origin;
+        let to = to + line[to..].chars().next().map_or(0, char::len_utf8);
+        Some(DigitMatch {
+            value,
+            column: line[..from].chars().count(),
+            word: line[from..to].to_string(),
+        })
+    }
+
+    /// The word starting earliest, the longest of those starting there.
+    fn first(&self, line: &str) -> Option<DigitMatch> {
+        let units = self.normalize(line);
+        let mut current = 0;
+        let mut best: Option<(usize, usize, u32)> = None;
+
+        for (index, unit) in units.iter().enumerate() {
+            // Words ending from here on start after the best one.
+            if matches!(best, Some((start, _, _)) if index + 1 > start + self.longest) {
+                break;
+            }
 
-    for index in 0..line.len() {
-        if let Some(digit) = get_digit(index, &current_line) {
-            match first_digit {
-                None => {
-                    first_digit = Some(digit);
-                    last_digit = digit;
+            current = self.forward.step(current, unit.character);
+            if !unit.ends {
+                continue;
+            }
+            for (len, value) in self.forward.nodes[current].outputs.iter() {
+                let start = index + 1 - len;
+                if !units[start].starts {
+                    continue;
+                }
+                if best.is_none_or(|(best_start, best_end, _)| {
+                    start < best_start || (start == best_start && index + 1 > best_end)
+                }) {
+                    best = Some((start, index + 1, *value));
                 }
-                Some(_) => last_digit = digit,
             }
         }
+
+        let (start, end, value) = best?;
+        Self::digit_match(line, &units, start, end, value)
+    }
+
+    /// The word starting latest, the longest of those starting there.
+    fn last(&self, line: &str) -> Option<DigitMatch> {
+        let units = self.normalize(line);
+        let mut current = 0;
+
+        for (start, unit) in units.iter().enumerate().rev() {
+            current = self.backward.step(current, unit.character);
+            if !unit.starts {
+                continue;
+            }
+            let found = self.backward.nodes[current]
+                .outputs
+                .iter()
+                .filter(|(len, _)| units[start + len - 1].ends)
+                .max_by_key(|(len, _)| *len);
+            if let Some((len, value)) = found {
+                return Self::digit_match(line, &units, start, start + len, *value);
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DigitMatch {
+    pub value: u32,
+    /// Position in characters from the start of the line.
+    pub column: usize,
+    pub word: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CalibrationValue {
+    /// 1-based line number.
+    pub line: usize,
+    pub value: u32,
+    pub first: DigitMatch,
+    pub last: DigitMatch,
+}
+
+impl std::fmt::Display for CalibrationValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} (first '{}' at {}, last '{}' at {})",
+            self.line,
+            self.value,
+            self.first.word,
+            self.first.column,
+            self.last.word,
+            self.last.column
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Calibration {
+    pub values: Vec<CalibrationValue>,
+    /// 1-based numbers of the lines without any digit.
+    pub skipped: Vec<usize>,
+    /// 1-based numbers of the lines whose value does not fit in `u32`, which
+    /// custom vocabularies with large values can produce.
+    pub overflowed: Vec<usize>,
+}
+
+impl Calibration {
+    pub fn sum(&self) -> u64 {
+        self.values.iter().map(|it| u64::from(it.value)).sum()
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let skipped = self
+            .skipped
+            .iter()
+            .map(|line| format!("line {} has no digit and is skipped", line));
+        let overflowed = self
+            .overflowed
+            .iter()
+            .map(|line| format!("line {} does not fit in u32 and is skipped", line));
+
+        skipped.chain(overflowed).collect()
+    }
+}
+
+/// The calibration value of every line, read with `vocabulary`.
+pub fn calibrate(lines: &[String], vocabulary: &Vocabulary) -> Calibration {
+    let matcher = DigitMatcher::new(vocabulary);
+    let mut calibration = Calibration::default();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some((first, last)) = matcher.first(line).zip(matcher.last(line)) else {
+            calibration.skipped.push(index + 1);
+            continue;
+        };
+
+        match first
+            .value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(last.value))
+        {
+            Some(value) => calibration.values.push(CalibrationValue {
+                line: index + 1,
+                value,
+                first,
+                last,
+            }),
+            None => calibration.overflowed.push(index + 1),
+        }
     }
 
-    first_digit.map(|it| it * 10 + last_digit)
+    calibration
 }
 
-fn sum_values_part_2(lines: Vec<String>) -> u32 {
-    return lines
-        .into_iter()
-        .filter_map(parse_calibration_value_advanced)
-        .sum();
+pub fn sum_spelled_values(lines: Vec<String>, vocabulary: &Vocabulary) -> u64 {
+    calibrate(&lines, vocabulary).sum()
 }
 
-pub fn sum_calibration_values(part: u32, lines: Vec<String>) -> u32 {
+pub fn sum_calibration_values(part: u32, lines: Vec<String>) -> u64 {
     match part {
-        1 => sum_values_part_1(lines),
-        2 => sum_values_part_2(lines),
+        1 => sum_digit_values(lines, false),
+        2 => sum_spelled_values(lines, &Vocabulary::english()),
         _ => 0,
     }
 }
@@ -182,4 +648,222 @@ mod test {
                 + 24
         )
     }
+
+    #[test]
+    fn matcher_overlapping_words() {
+        // Given
+        let matcher = DigitMatcher::new(&Vocabulary::english());
+
+        // When - Then
+        assert_eq!(
+            Some((8, "eight".to_string())),
+            matcher.first("eightwo").map(|it| (it.value, it.word))
+        );
+        assert_eq!(
+            Some((2, "two".to_string())),
+            matcher.last("eightwo").map(|it| (it.value, it.word))
+        );
+        assert_eq!(Some(1), matcher.last("xtwone").map(|it| it.value));
+        assert_eq!(Some(9), matcher.first("nnine").map(|it| it.value));
+        assert_eq!(None, matcher.first("zero0ten"));
+    }
+
+    #[test]
+    fn matcher_prefers_longest_word() {
+        // Given
+        let matcher = DigitMatcher::new(&Vocabulary::new([
+            ("eight", 8),
+            ("eighteen", 18),
+            ("teen", 10),
+        ]));
+
+        // When - Then
+        assert_eq!(Some(18), matcher.first("xeighteen").map(|it| it.value));
+        assert_eq!(Some(10), matcher.last("xeighteen").map(|it| it.value));
+        assert_eq!(Some(8), matcher.first("eighteex").map(|it| it.value));
+    }
+
+    #[test]
+    fn matcher_prefers_earliest_start_over_earliest_end() {
+        // Given
+        let matcher = DigitMatcher::new(&Vocabulary::new([("abcd", 1), ("bc", 2), ("cdx", 3)]));
+
+        // When
+        let first = matcher.first("xabcdx").unwrap();
+        let last = matcher.last("xabcdx").unwrap();
+
+        // Then
+        assert_eq!(
+            (1, 1, "abcd"),
+            (first.value, first.column, first.word.as_str())
+        );
+        assert_eq!((3, 3, "cdx"), (last.value, last.column, last.word.as_str()));
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        // Given
+        let vocabulary: Vocabulary = "zero=0, ten=10, one=1, 1=1".parse().unwrap();
+        let lines = vec![
+            "xtenzerone".to_string(),
+            "zeroxone".to_string(),
+            "1two".to_string(),
+            "two".to_string(),
+        ];
+
+        // When
+        let sum = sum_spelled_values(lines, &vocabulary);
+
+        // Then
+        assert_eq!(10 * 10 + 1 + 1 + 11, sum);
+    }
+
+    #[test]
+    fn named_vocabularies() {
+        // Given
+        let lines = vec!["xåttaett".to_string(), "fünfzweins".to_string()];
+
+        // When
+        let swedish = sum_spelled_values(lines.clone(), &"swedish".parse().unwrap());
+        let german = sum_spelled_values(lines, &"german".parse().unwrap());
+
+        // Then
+        assert_eq!(81, swedish);
+        assert_eq!(51, german);
+        assert!("klingon".parse::<Vocabulary>().is_err());
+    }
+
+    #[test]
+    fn case_insensitive_vocabulary() {
+        // Given
+        let lines = vec!["xEighTwo".to_string(), "NINE".to_string()];
+
+        // When
+        let sensitive = sum_spelled_values(lines.clone(), &Vocabulary::english());
+        let insensitive = sum_spelled_values(lines, &Vocabulary::english().ignoring_case());
+
+        // Then
+        assert_eq!(0, sensitive);
+        assert_eq!(82 + 99, insensitive);
+    }
+
+    #[test]
+    fn multi_byte_text() {
+        // Given
+        let lines = vec![
+            "🎄one👍2🎅".to_string(),
+            "crème brûlée seven🙂".to_string(),
+            "é8ñ".to_string(),
+            "👨‍👩‍👧".to_string(),
+        ];
+
+        // When
+        let part1 = sum_calibration_values(1, lines.clone());
+        let part2 = sum_calibration_values(2, lines);
+
+        // Then
+        assert_eq!(22 + 88, part1);
+        assert_eq!(12 + 77 + 88, part2);
+    }
+
+    #[test]
+    fn unicode_digits() {
+        // Given
+        let lines = vec!["٣x5".to_string(), "fiveⅦ７".to_string()];
+
+        // When
+        let ignored = sum_digit_values(lines.clone(), false);
+        let recognised = sum_digit_values(lines.clone(), true);
+        let spelled_ignored = sum_spelled_values(lines.clone(), &Vocabulary::english());
+        let spelled_recognised =
+            sum_spelled_values(lines, &Vocabulary::english().with_unicode_digits());
+
+        // Then
+        assert_eq!(55, ignored);
+        assert_eq!(35 + 77, recognised);
+        assert_eq!(55 + 55, spelled_ignored);
+        assert_eq!(35 + 57, spelled_recognised);
+    }
+
+    #[test]
+    fn calibration_explanation() {
+        // Given
+        let lines = vec![
+            "xtwone3four".to_string(),
+            "nothing here".to_string(),
+            "é7".to_string(),
+        ];
+
+        // When
+        let calibration = calibrate(&lines, &Vocabulary::english());
+
+        // Then
+        assert_eq!(
+            vec![
+                CalibrationValue {
+                    line: 1,
+                    value: 24,
+                    first: DigitMatch {
+                        value: 2,
+                        column: 1,
+                        word: "two".to_string()
+                    },
+                    last: DigitMatch {
+                        value: 4,
+                        column: 7,
+                        word: "four".to_string()
+                    },
+                },
+                CalibrationValue {
+                    line: 3,
+                    value: 77,
+                    first: DigitMatch {
+                        value: 7,
+                        column: 1,
+                        word: "7".to_string()
+                    },
+                    last: DigitMatch {
+                        value: 7,
+                        column: 1,
+                        word: "7".to_string()
+                    },
+                },
+            ],
+            calibration.values
+        );
+        assert_eq!(vec![2], calibration.skipped);
+        assert_eq!(
+            vec!["line 2 has no digit and is skipped"],
+            calibration.warnings()
+        );
+        assert_eq!(
+            "line 1: 24 (first 'two' at 1, last 'four' at 7)",
+            calibration.values[0].to_string()
+        );
+    }
+
+    #[test]
+    fn large_values_overflow() {
+        // Given
+        let lines = vec![
+            "xoneeight".to_string(),
+            "ten".to_string(),
+            "one".to_string(),
+        ];
+        let vocabulary: Vocabulary = "one=4000000000,eight=8,ten=10".parse().unwrap();
+
+        // When
+        let calibration = calibrate(&lines, &vocabulary);
+
+        // Then
+        assert_eq!(110, calibration.sum());
+        assert_eq!(vec![1, 3], calibration.overflowed);
+        assert_eq!(
+            vec![
+                "line 1 does not fit in u32 and is skipped",
+                "line 3 does not fit in u32 and is skipped"
+            ],
+            calibration.warnings()
+        );
+    }
 }
@@ -2,21 +2,115 @@ use std::str::FromStr;
 
 use crate::Error;
 
-struct Set {
-    blue: u32,
-    green: u32,
-    red: u32,
+/// Cube counts per colour, in the order the colours were first given.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Set {
+    cubes: Vec<(String, u32)>,
 }
 
-struct Game {
-    id: u32,
-    sets: Vec<Set>,
+impl Set {
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes
+            .iter()
+            .find(|(it, _)| it == color)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn insert(&mut self, color: &str, count: u32) {
+        match self.cubes.iter_mut().find(|(it, _)| it == color) {
+            Some((_, current)) => *current = count,
+            None => self.cubes.push((color.to_string(), count)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Whether the set could be drawn from `bag`, colours missing from the bag
+    /// have no cubes at all.
+    pub fn fits_in(&self, bag: &Set) -> bool {
+        self.iter().all(|(color, count)| count <= bag.get(color))
+    }
+
+    pub fn power(&self) -> Result<u32, Error> {
+        self.iter()
+            .map(|(_, count)| if count == 0 { 1 } else { count })
+            .try_fold(1_u32, |power, count| power.checked_mul(count))
+            .ok_or_else(|| Error::Overflow(format!("Power of {} does not fit in u32", self)))
+    }
+
+    /// In strict mode a colour may only be given once per set, otherwise a
+    /// repeated colour keeps the last count. An empty set is an error either
+    /// way, strict mode only names it as such.
+    pub fn parse(set: &str, strict: bool) -> anyhow::Result<Self> {
+        let mut parsed_set = Set::default();
+        if strict && set.trim().is_empty() {
+            Err(Error::InvalidInput("Empty set".to_string()))?
+        }
+
+        for cube_count in set.split(',').map(|it| it.trim()) {
+            let (count_str, color) = cube_count.split_once(' ').ok_or(Error::InvalidInput(
+                "Missing whitespace in cube count".to_string(),
+            ))?;
+            let count: u32 = count_str.parse()?;
+            let color = color.trim();
+            if color.is_empty() {
+                Err(Error::InvalidInput(
+                    "Missing color in cube count".to_string(),
+                ))?
+            }
+            if strict && parsed_set.iter().any(|(it, _)| it == color) {
+                Err(Error::InvalidInput(format!(
+                    "Color {} appears twice in set",
+                    color
+                )))?
+            }
+            parsed_set.insert(color, count);
+        }
+        Ok(parsed_set)
+    }
 }
 
-impl FromStr for Game {
+impl FromStr for Set {
     type Err = anyhow::Error;
 
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
+    fn from_str(set: &str) -> Result<Self, Self::Err> {
+        Set::parse(set, false)
+    }
+}
+
+impl std::fmt::Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (color, count)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, color)?;
+        }
+        Ok(())
+    }
+}
+
+/// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+pub fn default_bag() -> Set {
+    let mut bag = Set::default();
+    bag.insert("red", 12);
+    bag.insert("green", 13);
+    bag.insert("blue", 14);
+    bag
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Set>,
+}
+
+impl Game {
+    pub fn parse(line: &str, strict: bool) -> anyhow::Result<Self> {
         let (id_str, sets) = line
             .split_once(':')
             .ok_or(Error::InvalidInput("Missing :".to_string()))?;
@@ -28,88 +122,295 @@ impl FromStr for Game {
             .1
             .parse()?;
 
-        let mut parsed_sets: Vec<Set> = Vec::new();
-        for set in sets.split(';') {
-            let mut parsed_set = Set {
-                blue: 0,
-                green: 0,
-                red: 0,
-            };
-            for cube_count in set.split(',').map(|it| it.trim()) {
-                let (count_str, color) = cube_count.split_once(' ').ok_or(Error::InvalidInput(
-                    "Missing whitespace in cube count".to_string(),
-                ))?;
-                let count: u32 = count_str.parse()?;
-                match color {
-                    "blue" => {
-                        parsed_set.blue = count;
-                    }
-                    "red" => {
-                        parsed_set.red = count;
-                    }
-                    "green" => {
-                        parsed_set.green = count;
-                    }
-                    _ => Err(Error::InvalidInput(
-                        "Invalid color in cube count".to_string(),
-                    ))?,
+        Ok(Game {
+            id,
+            sets: sets
+                .split(';')
+                .map(|set| Set::parse(set, strict))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// The fewest cubes of each colour that makes every set possible.
+    pub fn min_bag(&self) -> Set {
+        let mut bag = Set::default();
+        for set in self.sets.iter() {
+            for (color, count) in set.iter() {
+                if count >= bag.get(color) {
+                    bag.insert(color, count);
                 }
             }
-            parsed_sets.push(parsed_set);
         }
-        Ok(Game {
-            id,
-            sets: parsed_sets,
+        bag
+    }
+
+    pub fn report(&self, bag: &Set) -> GameReport {
+        let min_bag = self.min_bag();
+
+        let violations = min_bag
+            .iter()
+            .filter(|(color, count)| *count > bag.get(color))
+            .map(|(color, count)| Violation {
+                color: color.to_string(),
+                count,
+                limit: bag.get(color),
+                set: self
+                    .sets
+                    .iter()
+                    .position(|set| set.get(color) == count)
+                    .map_or(0, |index| index + 1),
+            })
+            .collect();
+
+        GameReport {
+            id: self.id,
+            min_bag,
+            violations,
+        }
+    }
+}
+
+/// A colour in a game that exceeds the bag.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Violation {
+    pub color: String,
+    pub count: u32,
+    pub limit: u32,
+    /// 1-based position of the first set that draws `count` cubes.
+    pub set: usize,
+}
+
+impl Violation {
+    pub fn excess(&self) -> u32 {
+        self.count - self.limit
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameReport {
+    pub id: u32,
+    pub min_bag: Set,
+    pub violations: Vec<Violation>,
+}
+
+impl GameReport {
+    pub fn is_possible(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn violated_colors(&self) -> Vec<&str> {
+        self.violations
+            .iter()
+            .map(|violation| violation.color.as_str())
+            .collect()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if control.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub fn report_json(reports: &[GameReport]) -> Result<String, Error> {
+    let games: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let min_bag: Vec<String> = report
+                .min_bag
+                .iter()
+                .map(|(color, count)| format!("{}:{}", json_string(color), count))
+                .collect();
+            let violations: Vec<String> = report
+                .violations
+                .iter()
+                .map(|violation| {
+                    format!(
+                        "{{\"color\":{},\"count\":{},\"limit\":{},\"excess\":{},\"set\":{}}}",
+                        json_string(&violation.color),
+                        violation.count,
+                        violation.limit,
+                        violation.excess(),
+                        violation.set
+                    )
+                })
+                .collect();
+
+            Ok(format!(
+                "{{\"id\":{},\"min_bag\":{{{}}},\"power\":{},\"possible\":{},\"violations\":[{}]}}",
+                report.id,
+                min_bag.join(","),
+                report.min_bag.power()?,
+                report.is_possible(),
+                violations.join(",")
+            ))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(format!("[{}]", games.join(",\n")))
+}
+
+pub fn report_table(reports: &[GameReport]) -> Result<String, Error> {
+    let rows: Vec<[String; 4]> = reports
+        .iter()
+        .map(|report| {
+            let violations: Vec<String> = report
+                .violations
+                .iter()
+                .map(|violation| {
+                    format!(
+                        "{} +{} (set {})",
+                        violation.color,
+                        violation.excess(),
+                        violation.set
+                    )
+                })
+                .collect();
+
+            Ok([
+                report.id.to_string(),
+                report.min_bag.to_string(),
+                report.min_bag.power()?.to_string(),
+                if violations.is_empty() {
+                    "-".to_string()
+                } else {
+                    violations.join(", ")
+                },
+            ])
+        })
+        .collect::<Result<_, Error>>()?;
+
+    let header = ["game", "min bag", "power", "violations"].map(|it| it.to_string());
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .map(|row| row[column].len())
+                .max()
+                .unwrap_or(0)
         })
+        .collect();
+
+    Ok(std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            format!(
+                "{:>w0$} | {:<w1$} | {:>w2$} | {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+pub fn reports(lines: Vec<String>, config: &Config) -> anyhow::Result<Vec<GameReport>> {
+    parse_games(lines, config.strict)
+        .map(|game| Ok(game?.report(&config.bag)))
+        .collect()
+}
+
+impl FromStr for Game {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Game::parse(line, false)
     }
 }
 
-fn parse_games(lines: Vec<String>) -> impl Iterator<Item = anyhow::Result<Game>> {
-    lines.into_iter().map(|line| line.parse())
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, " {}", set)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_games(lines: Vec<String>, strict: bool) -> impl Iterator<Item = anyhow::Result<Game>> {
+    lines
+        .into_iter()
+        .map(move |line| Game::parse(&line, strict))
+}
+
+fn add_checked(sum: u32, value: u32) -> Result<u32, Error> {
+    sum.checked_add(value)
+        .ok_or_else(|| Error::Overflow(format!("Sum {} + {} does not fit in u32", sum, value)))
 }
 
-fn sum_valid_ids<Iter: Iterator<Item = anyhow::Result<Game>>>(games: Iter) -> anyhow::Result<u32> {
+fn sum_valid_ids<Iter: Iterator<Item = anyhow::Result<Game>>>(
+    games: Iter,
+    bag: &Set,
+) -> anyhow::Result<u32> {
     let mut sum = 0;
     for game_res in games {
         let game = game_res?;
-        if game
-            .sets
-            .iter()
-            .all(|set| set.blue <= 14 && set.green <= 13 && set.red <= 12)
-        {
-            sum += game.id
+        if game.sets.iter().all(|set| set.fits_in(bag)) {
+            sum = add_checked(sum, game.id)?;
         }
     }
     Ok(sum)
 }
 
-fn max_by<F: Fn(&Set) -> u32>(sets: &Vec<Set>, by_key: F) -> u32 {
-    sets.iter()
-        .map(by_key)
-        .max()
-        .map(|min| if min == 0 { 1 } else { min })
-        .unwrap_or(1)
-}
-
 fn power_of_min<Iter: Iterator<Item = anyhow::Result<Game>>>(games: Iter) -> anyhow::Result<u32> {
     let mut sum = 0;
     for game_res in games {
-        let game = game_res?;
-        let red = max_by(&game.sets, |set| set.red);
-        let blue = max_by(&game.sets, |set| set.blue);
-        let green = max_by(&game.sets, |set| set.green);
-
-        sum += red * green * blue;
+        sum = add_checked(sum, game_res?.min_bag().power()?)?;
     }
 
     Ok(sum)
 }
 
+pub struct Config {
+    pub bag: Set,
+    pub strict: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bag: default_bag(),
+            strict: false,
+        }
+    }
+}
+
 pub fn elf_challenge(part: u32, lines: Vec<String>) -> anyhow::Result<u32> {
-    let games = parse_games(lines);
+    elf_challenge_with_config(part, lines, &Config::default())
+}
+
+pub fn elf_challenge_with_config(
+    part: u32,
+    lines: Vec<String>,
+    config: &Config,
+) -> anyhow::Result<u32> {
+    let games = parse_games(lines, config.strict);
 
     Ok(match part {
-        1 => sum_valid_ids(games)?,
+        1 => sum_valid_ids(games, &config.bag)?,
         2 => power_of_min(games)?,
         _ => 0,
     })
@@ -117,6 +418,8 @@ pub fn elf_challenge(part: u32, lines: Vec<String>) -> anyhow::Result<u32> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -156,4 +459,204 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
         // Then
         assert_eq!(2286, sum);
     }
+
+    #[test]
+    fn custom_bag_and_colors() {
+        // Given
+        let input = "Game 1: 3 blue, 4 yellow; 1 red
+Game 2: 2 purple, 5 red; 1 purple
+Game 3: 7 blue"
+            .lines()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>();
+        let bag: Set = "5 red, 4 yellow, 7 blue".parse().unwrap();
+        let config = Config { bag, strict: true };
+
+        // When
+        let sum = elf_challenge_with_config(1, input.clone(), &config).unwrap();
+        let power = elf_challenge_with_config(2, input, &config).unwrap();
+
+        // Then
+        assert_eq!(1 + 3, sum);
+        assert_eq!(3 * 4 + 2 * 5 + 7, power);
+    }
+
+    #[test]
+    fn min_bag_covers_all_colors() {
+        // Given
+        let game: Game = "Game 7: 1 red, 0 green; 3 cyan, 2 red; 4 cyan"
+            .parse()
+            .unwrap();
+
+        // When
+        let min_bag = game.min_bag();
+
+        // Then
+        assert_eq!(
+            vec![("red", 2), ("green", 0), ("cyan", 4)],
+            min_bag.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(2 * 4, min_bag.power().unwrap());
+    }
+
+    #[test]
+    fn power_overflow() {
+        // Given
+        let many = vec!["Game 1: 100 a, 100 b, 100 c, 100 d, 100 e".to_string()];
+        let sum = vec![
+            "Game 1: 65536 a, 65535 b".to_string(),
+            "Game 2: 65536 a, 65535 b".to_string(),
+        ];
+
+        // When
+        let many_result = elf_challenge(2, many);
+        let sum_result = elf_challenge(2, sum);
+
+        // Then
+        assert_eq!(
+            "Overflow: Power of 100 a, 100 b, 100 c, 100 d, 100 e does not fit in u32",
+            many_result.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Overflow: Sum 4294901760 + 4294901760 does not fit in u32",
+            sum_result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn strict_rejects_duplicates_and_empty_sets() {
+        // Given
+        let duplicate = "Game 1: 3 blue, 4 red, 1 blue";
+        let empty = "Game 2: 3 blue;; 4 red";
+
+        // When
+        let lenient_duplicate: Game = duplicate.parse().unwrap();
+        let lenient_empty = empty.parse::<Game>();
+
+        // Then
+        assert_eq!(1, lenient_duplicate.sets[0].get("blue"));
+        assert_eq!(
+            "Invalid input: Missing whitespace in cube count",
+            lenient_empty.unwrap_err().to_string()
+        );
+        assert!(Game::parse(duplicate, true).is_err());
+        assert_eq!(
+            "Invalid input: Empty set",
+            Game::parse(empty, true).unwrap_err().to_string()
+        );
+        assert!("Game 3: ".parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn display_reproduces_input() {
+        // Given
+        let line = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+        // When
+        let game = Game::parse(line, true).unwrap();
+
+        // Then
+        assert_eq!(line, game.to_string());
+    }
+
+    fn arb_set() -> impl Strategy<Value = Set> {
+        proptest::collection::btree_map("[a-z]{1,8}( [a-z]{1,8})?", any::<u32>(), 1..5).prop_map(
+            |cubes| {
+                let mut set = Set::default();
+                for (color, count) in cubes {
+                    set.insert(&color, count);
+                }
+                set
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn parse_display_round_trip(
+            id in any::<u32>(),
+            sets in proptest::collection::vec(arb_set(), 1..6),
+        ) {
+            let game = Game { id, sets };
+
+            let line = game.to_string();
+
+            prop_assert_eq!(&game, &Game::parse(&line, true).unwrap());
+            prop_assert_eq!(&game, &line.parse::<Game>().unwrap());
+        }
+    }
+
+    #[test]
+    fn game_report() {
+        // Given
+        let games = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            .lines()
+            .map(|it| it.to_string())
+            .collect();
+
+        // When
+        let reports = reports(games, &Config::default()).unwrap();
+
+        // Then
+        assert_eq!(
+            vec![Violation {
+                color: "red".to_string(),
+                count: 20,
+                limit: 12,
+                set: 1
+            }],
+            reports[0].violations
+        );
+        assert_eq!(vec!["red", "blue"], reports[1].violated_colors());
+        assert_eq!(3, reports[1].violations[1].set);
+        assert!(reports[2].is_possible());
+        assert_eq!(
+            vec![3],
+            reports
+                .iter()
+                .filter(|report| report.violated_colors() == ["red"])
+                .map(|report| report.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn report_output() {
+        // Given
+        let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse()
+            .unwrap();
+        let bag: Set = "5 blue".parse().unwrap();
+
+        // When
+        let reports = vec![game.report(&bag)];
+
+        // Then
+        assert_eq!(
+            "game | min bag                | power | violations\n   \
+                1 | 6 blue, 4 red, 2 green |    48 | blue +1 (set 2), red +4 (set 1), green +2 (set 2)",
+            report_table(&reports).unwrap()
+        );
+        assert_eq!(
+            "[{\"id\":1,\"min_bag\":{\"blue\":6,\"red\":4,\"green\":2},\"power\":48,\"possible\":false,\"violations\":[\
+                {\"color\":\"blue\",\"count\":6,\"limit\":5,\"excess\":1,\"set\":2},\
+                {\"color\":\"red\",\"count\":4,\"limit\":0,\"excess\":4,\"set\":1},\
+                {\"color\":\"green\",\"count\":2,\"limit\":0,\"excess\":2,\"set\":2}]}]",
+            report_json(&reports).unwrap()
+        );
+    }
+
+    #[test]
+    fn report_json_escapes_control_characters() {
+        // Given
+        let game: Game = "Game 1: 3 blue\tx, 4 r\"e\\d\u{1}".parse().unwrap();
+
+        // When
+        let json = report_json(&[game.report(&default_bag())]).unwrap();
+
+        // Then
+        assert!(json.contains("{\"blue\\tx\":3,\"r\\\"e\\\\d\\u0001\":4}"));
+    }
 }
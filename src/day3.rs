@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::str::FromStr;
 
-use crate::math::Point;
+use crate::{
+    math::{Grid, Point},
+    Error,
+};
 
 #[derive(Debug, PartialEq)]
 struct EngineNumber {
@@ -9,65 +12,50 @@ struct EngineNumber {
     value: u32,
 }
 
+/// A symbol and its position.
+type Symbol = (Point, char);
+
 #[derive(Debug, PartialEq)]
 enum SchematicPart {
     Number(EngineNumber),
     Symbol(Point, char),
 }
 
-fn to_digit(byte: u8) -> Option<u32> {
-    if 48 <= byte && byte <= 57 {
-        Some((byte - 48).into())
-    } else {
-        None
-    }
-}
-
 fn parse_schematic_part(
-    line: &[u8],
+    line: &[char],
     start_index: usize,
     y: u32,
+    blank: char,
 ) -> anyhow::Result<(Option<SchematicPart>, usize)> {
-    for i in start_index..line.len() {
-        if line[i] == '.' as u8 {
+    for (i, character) in line.iter().enumerate().skip(start_index) {
+        if *character == blank {
             continue;
         }
 
-        if let Some(digit) = to_digit(line[i]) {
-            let mut digits = vec![digit];
-            for digit_index in i + 1..line.len() {
-                match to_digit(line[digit_index]) {
-                    Some(next_digit) => {
-                        digits.push(next_digit);
-                    }
-                    None => break,
-                }
-            }
-            let mut value = 0;
+        let start = Point::new(i.try_into()?, y.try_into()?);
 
-            for (digit_index, digit) in digits.iter().rev().enumerate() {
-                value += digit * 10_u32.pow(digit_index.try_into()?);
-            }
+        if character.is_ascii_digit() {
+            let digits: Vec<u32> = line[i..].iter().map_while(|it| it.to_digit(10)).collect();
+
+            let value = digits
+                .iter()
+                .try_fold(0_u32, |value, digit| {
+                    value.checked_mul(10)?.checked_add(*digit)
+                })
+                .ok_or_else(|| {
+                    Error::Overflow(format!("Number at {} does not fit in u32", start))
+                })?;
 
             return Ok((
                 Some(SchematicPart::Number(EngineNumber {
-                    start: Point {
-                        x: i.try_into()?,
-                        y: y.try_into()?,
-                    },
+                    start,
                     len: digits.len().try_into()?,
                     value,
                 })),
                 i + digits.len(),
             ));
         } else {
-            return Ok((
-                Some(SchematicPart::Symbol(
-                    Point::new(i.try_into()?, y.try_into()?),
-                    line[i] as char,
-                )),
-                i + 1,
-            ));
+            return Ok((Some(SchematicPart::Symbol(start, *character)), i + 1));
         }
     }
 
@@ -75,92 +63,362 @@ fn parse_schematic_part(
 }
 
 fn parse_schematic(
-    input: Vec<String>,
-) -> anyhow::Result<(Vec<EngineNumber>, HashMap<Point, char>)> {
+    chars: &Grid<char>,
+    blank: char,
+) -> anyhow::Result<(Vec<EngineNumber>, Vec<Symbol>)> {
     let mut numbers: Vec<EngineNumber> = Vec::new();
-    let mut symbols: HashMap<Point, char> = HashMap::new();
-    let mut y = 0;
-    for line in input {
+    let mut symbols: Vec<Symbol> = Vec::new();
+    for (y, line) in chars.rows().enumerate() {
         let mut index = 0;
-        let byte_line = line.as_bytes();
 
-        while index < byte_line.len() {
-            let (part, next_index) = parse_schematic_part(byte_line, index, y)?;
+        while index < line.len() {
+            let (part, next_index) = parse_schematic_part(line, index, y.try_into()?, blank)?;
 
             index = next_index;
 
             match part {
                 Some(part) => match part {
                     SchematicPart::Number(num) => numbers.push(num),
-                    SchematicPart::Symbol(pos, symbol) => {
-                        symbols.insert(pos, symbol);
-                    }
+                    SchematicPart::Symbol(pos, symbol) => symbols.push((pos, symbol)),
                 },
                 None => break,
             };
         }
-        y += 1;
     }
 
     Ok((numbers, symbols))
 }
 
-fn is_part_number(number: &EngineNumber, symbols: &HashMap<Point, char>) -> bool {
-    for i in -1..number.len as i32 + 1 {
-        if symbols.contains_key(&(number.start + Point::new(i, -1)))
-            || symbols.contains_key(&(number.start + Point::new(i, 0)))
-            || symbols.contains_key(&(number.start + Point::new(i, 1)))
-        {
-            return true;
+const PART_COLOR: &str = "\x1b[32m";
+const NON_PART_COLOR: &str = "\x1b[31m";
+const GEAR_COLOR: &str = "\x1b[1;33m";
+const RESET_COLOR: &str = "\x1b[0m";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Cell {
+    Blank,
+    Number(usize),
+    Symbol(char),
+}
+
+/// Dense index over the schematic where every cell knows the number or symbol
+/// occupying it, so neighbour lookups do not depend on the schematic size.
+struct Schematic {
+    blank: char,
+    cells: Grid<Cell>,
+    numbers: Vec<EngineNumber>,
+    symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    fn parse(input: Vec<String>, blank: char) -> anyhow::Result<Self> {
+        let chars = Grid::parse(&input, Ok)?;
+        let (numbers, symbols) = parse_schematic(&chars, blank)?;
+
+        let mut cells = chars.map(|_, _| Cell::Blank);
+        for (index, num) in numbers.iter().enumerate() {
+            for i in 0..num.len as i32 {
+                cells[num.start + Point::new(i, 0)] = Cell::Number(index);
+            }
+        }
+        for (pos, symbol) in symbols.iter() {
+            cells[*pos] = Cell::Symbol(*symbol);
+        }
+
+        Ok(Schematic {
+            blank,
+            cells,
+            numbers,
+            symbols,
+        })
+    }
+
+    fn get(&self, pos: Point) -> Cell {
+        self.cells.get(pos).copied().unwrap_or(Cell::Blank)
+    }
+
+    fn is_part_number(&self, number: &EngineNumber) -> bool {
+        (-1..number.len as i32 + 1).any(|i| {
+            (-1..=1).any(|dy| matches!(self.get(number.start + Point::new(i, dy)), Cell::Symbol(_)))
+        })
+    }
+
+    /// Numbers touching `pos` in reading order, each number once even when
+    /// several of its digits are adjacent.
+    fn adjacent_numbers(&self, pos: Point) -> Vec<&EngineNumber> {
+        let mut indices: Vec<usize> = Vec::new();
+        for (_, cell) in self.cells.neighbors8(pos) {
+            if let Cell::Number(index) = cell {
+                if !indices.contains(index) {
+                    indices.push(*index);
+                }
+            }
         }
+        indices.sort_unstable();
+
+        indices
+            .into_iter()
+            .map(|index| &self.numbers[index])
+            .collect()
     }
-    false
+
+    fn gear(
+        &self,
+        position: Point,
+        symbol: char,
+        rules: &GearRules,
+    ) -> anyhow::Result<Option<Gear>> {
+        if !rules.symbols.contains(&symbol) {
+            return Ok(None);
+        }
+
+        let numbers: Vec<u32> = self
+            .adjacent_numbers(position)
+            .iter()
+            .map(|num| num.value)
+            .collect();
+
+        if !rules.adjacent.accepts(numbers.len()) {
+            return Ok(None);
+        }
+
+        let power = rules.combine.apply(&numbers).ok_or_else(|| {
+            Error::Overflow(format!("Power of gear at {} does not fit in u32", position))
+        })?;
+
+        Ok(Some(Gear {
+            position,
+            symbol,
+            numbers,
+            power,
+        }))
+    }
+
+    /// The schematic with part numbers in green, other numbers in red and
+    /// gears in yellow.
+    fn render(&self, rules: &GearRules) -> anyhow::Result<String> {
+        let is_part: Vec<bool> = self
+            .numbers
+            .iter()
+            .map(|num| self.is_part_number(num))
+            .collect();
+        let mut gears: Vec<Point> = Vec::new();
+        for (pos, symbol) in self.symbols.iter() {
+            if self.gear(*pos, *symbol, rules)?.is_some() {
+                gears.push(*pos);
+            }
+        }
+
+        let rendered = self.cells.map(|pos, cell| match cell {
+            Cell::Blank => self.blank.to_string(),
+            Cell::Number(index) => {
+                let num = &self.numbers[*index];
+                let digit = format!("{:0width$}", num.value, width = num.len as usize)
+                    .chars()
+                    .nth((pos.x - num.start.x) as usize)
+                    .unwrap_or('?');
+                let color = if is_part[*index] {
+                    PART_COLOR
+                } else {
+                    NON_PART_COLOR
+                };
+                format!("{}{}{}", color, digit, RESET_COLOR)
+            }
+            Cell::Symbol(symbol) if gears.contains(&pos) => {
+                format!("{}{}{}", GEAR_COLOR, symbol, RESET_COLOR)
+            }
+            Cell::Symbol(symbol) => symbol.to_string(),
+        });
+
+        Ok(rendered.to_string())
+    }
+}
+
+pub fn render(input: Vec<String>, config: &Config) -> anyhow::Result<String> {
+    Schematic::parse(input, config.blank)?.render(&config.gear_rules)
 }
 
-fn sum_part_numbers(input: Vec<String>) -> anyhow::Result<u32> {
-    let (numbers, symbols) = parse_schematic(input)?;
+/// Numbers without an adjacent symbol, with the position of their first digit.
+pub fn non_part_numbers(input: Vec<String>, config: &Config) -> anyhow::Result<Vec<(Point, u32)>> {
+    let schematic = Schematic::parse(input, config.blank)?;
 
-    Ok(numbers
+    Ok(schematic
+        .numbers
         .iter()
-        .filter(|num| is_part_number(num, &symbols))
-        .map(|num| num.value)
-        .sum())
+        .filter(|num| !schematic.is_part_number(num))
+        .map(|num| (num.start, num.value))
+        .collect())
 }
 
-fn gear_power(position: &Point, symbol: &char, numbers: &Vec<EngineNumber>) -> Option<u32> {
-    if *symbol != '*' {
-        return None;
+/// How many numbers a symbol needs to touch to be a gear.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AdjacentCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl AdjacentCount {
+    fn accepts(&self, count: usize) -> bool {
+        match self {
+            Self::Exactly(required) => count == *required,
+            Self::AtLeast(required) => count >= *required,
+        }
     }
+}
 
-    let adjacent_numbers: Vec<&EngineNumber> = numbers
-        .iter()
-        .filter(|num| {
-            (0..num.len)
-                .map(|i| num.start + Point::new(i as i32, 0))
-                .any(|num_pos| position.adjacent(&num_pos))
-        })
-        .collect();
+/// `2` for exactly two numbers and `2+` for two or more.
+impl FromStr for AdjacentCount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |count: &str| {
+            count.parse().map_err(|err| {
+                Error::InvalidInput(format!("Invalid adjacent count {}: {}", s, err))
+            })
+        };
 
-    if adjacent_numbers.len() != 2 {
-        return None;
+        match s.strip_suffix('+') {
+            Some(count) => Ok(Self::AtLeast(parse(count)?)),
+            None => Ok(Self::Exactly(parse(s)?)),
+        }
     }
+}
 
-    Some(adjacent_numbers.iter().map(|num| num.value).product())
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Combine {
+    Product,
+    Sum,
 }
 
-fn sum_gear_powers(input: Vec<String>) -> anyhow::Result<u32> {
-    let (numbers, symbols) = parse_schematic(input)?;
+impl Combine {
+    fn apply(&self, numbers: &[u32]) -> Option<u32> {
+        match self {
+            Self::Product => numbers
+                .iter()
+                .try_fold(1_u32, |acc, num| acc.checked_mul(*num)),
+            Self::Sum => numbers
+                .iter()
+                .try_fold(0_u32, |acc, num| acc.checked_add(*num)),
+        }
+    }
+}
 
-    Ok(symbols
+impl FromStr for Combine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Self::Product),
+            "sum" => Ok(Self::Sum),
+            other => Err(Error::InvalidInput(format!(
+                "{} is not a way to combine gear numbers",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GearRules {
+    pub symbols: Vec<char>,
+    pub adjacent: AdjacentCount,
+    pub combine: Combine,
+}
+
+/// The puzzle rules: a `*` touching exactly two numbers, multiplied together.
+impl Default for GearRules {
+    fn default() -> Self {
+        GearRules {
+            symbols: vec!['*'],
+            adjacent: AdjacentCount::Exactly(2),
+            combine: Combine::Product,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Gear {
+    pub position: Point,
+    pub symbol: char,
+    pub numbers: Vec<u32>,
+    pub power: u32,
+}
+
+impl std::fmt::Display for Gear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let numbers: Vec<String> = self.numbers.iter().map(|num| num.to_string()).collect();
+        write!(
+            f,
+            "{} {} {}: {}",
+            self.symbol,
+            self.position,
+            numbers.join(", "),
+            self.power
+        )
+    }
+}
+
+/// Every gear in the schematic, in reading order.
+pub fn gears(input: Vec<String>, config: &Config) -> anyhow::Result<Vec<Gear>> {
+    let schematic = Schematic::parse(input, config.blank)?;
+
+    let mut gears = Vec::new();
+    for (pos, symbol) in schematic.symbols.iter() {
+        if let Some(gear) = schematic.gear(*pos, *symbol, &config.gear_rules)? {
+            gears.push(gear);
+        }
+    }
+    Ok(gears)
+}
+
+fn add_checked(sum: u32, value: u32) -> Result<u32, Error> {
+    sum.checked_add(value)
+        .ok_or_else(|| Error::Overflow(format!("Sum {} + {} does not fit in u32", sum, value)))
+}
+
+fn sum_part_numbers(input: Vec<String>, config: &Config) -> anyhow::Result<u32> {
+    let schematic = Schematic::parse(input, config.blank)?;
+
+    Ok(schematic
+        .numbers
+        .iter()
+        .filter(|num| schematic.is_part_number(num))
+        .try_fold(0, |sum, num| add_checked(sum, num.value))?)
+}
+
+fn sum_gear_powers(input: Vec<String>, config: &Config) -> anyhow::Result<u32> {
+    Ok(gears(input, config)?
         .iter()
-        .filter_map(|(pos, symbol)| gear_power(pos, symbol, &numbers))
-        .sum())
+        .try_fold(0, |sum, gear| add_checked(sum, gear.power))?)
+}
+
+pub struct Config {
+    /// Character of the empty cells, `.` in the puzzle.
+    pub blank: char,
+    pub gear_rules: GearRules,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            blank: '.',
+            gear_rules: GearRules::default(),
+        }
+    }
 }
 
 pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<u32> {
+    challenge_with_config(part, input, &Config::default())
+}
+
+pub fn challenge_with_config(
+    part: u32,
+    input: Vec<String>,
+    config: &Config,
+) -> anyhow::Result<u32> {
     match part {
-        1 => sum_part_numbers(input),
-        2 => sum_gear_powers(input),
+        1 => sum_part_numbers(input, config),
+        2 => sum_gear_powers(input, config),
         _ => Ok(0),
     }
 }
@@ -172,12 +430,12 @@ mod tests {
     #[test]
     fn verify_parse_number() {
         // Given
-        let line = "...123".as_bytes();
+        let line: Vec<char> = "...123".chars().collect();
         let start_index = 0;
         let y = 0;
 
         // When
-        let (part, next_index) = parse_schematic_part(line, start_index, y).unwrap();
+        let (part, next_index) = parse_schematic_part(&line, start_index, y, '.').unwrap();
 
         // Then
         assert_eq!(next_index, 6);
@@ -194,12 +452,12 @@ mod tests {
     #[test]
     fn verify_parse_symbol() {
         // Given
-        let line = ".*".as_bytes();
+        let line: Vec<char> = ".*".chars().collect();
         let start_index = 0;
         let y = 0;
 
         // When
-        let (part, next_index) = parse_schematic_part(line, start_index, y).unwrap();
+        let (part, next_index) = parse_schematic_part(&line, start_index, y, '.').unwrap();
 
         // Then
         assert_eq!(next_index, 2);
@@ -211,10 +469,10 @@ mod tests {
         // Given
         let binding = vec!["467..114..", "...*......", "..35..633.", "......#..."];
 
-        let input = binding.iter().map(|line| line.to_string()).collect();
+        let input: Vec<String> = binding.iter().map(|line| line.to_string()).collect();
 
         // When
-        let (numbers, symbols) = parse_schematic(input).unwrap();
+        let (numbers, symbols) = parse_schematic(&Grid::parse(&input, Ok).unwrap(), '.').unwrap();
 
         // Then
         assert_eq!(
@@ -244,7 +502,7 @@ mod tests {
         );
 
         assert_eq!(
-            HashMap::from([(Point::new(3, 1), '*'), (Point::new(6, 3), '#')]),
+            vec![(Point::new(3, 1), '*'), (Point::new(6, 3), '#')],
             symbols
         );
     }
@@ -267,7 +525,7 @@ mod tests {
         let input = binding.iter().map(|line| line.to_string()).collect();
 
         // When
-        let sum = sum_part_numbers(input).unwrap();
+        let sum = sum_part_numbers(input, &Config::default()).unwrap();
 
         // Then
         assert_eq!(4361, sum);
@@ -296,4 +554,210 @@ mod tests {
         // Then
         assert_eq!(467835, sum);
     }
+
+    #[test]
+    fn verify_adjacent_numbers() {
+        // Given
+        let binding = vec!["467..114..", "...*......", "..35..633."];
+        let input = binding.iter().map(|line| line.to_string()).collect();
+
+        // When
+        let schematic = Schematic::parse(input, '.').unwrap();
+
+        // Then
+        assert_eq!(
+            vec![467, 35],
+            schematic
+                .adjacent_numbers(Point::new(3, 1))
+                .iter()
+                .map(|num| num.value)
+                .collect::<Vec<_>>()
+        );
+        assert!(schematic.adjacent_numbers(Point::new(0, 2)).is_empty());
+        assert_eq!(Cell::Blank, schematic.get(Point::new(-1, 0)));
+        assert_eq!(Cell::Blank, schematic.get(Point::new(10, 0)));
+        assert!(!schematic.is_part_number(&schematic.numbers[1]));
+    }
+
+    #[test]
+    fn verify_gear_rules() {
+        // Given
+        let binding = vec![
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+        let input: Vec<String> = binding.iter().map(|line| line.to_string()).collect();
+        let config = Config {
+            gear_rules: GearRules {
+                symbols: vec!['*', '#', '+'],
+                adjacent: "1+".parse().unwrap(),
+                combine: "sum".parse().unwrap(),
+            },
+            ..Default::default()
+        };
+
+        // When
+        let gears = gears(input.clone(), &config).unwrap();
+        let sum = challenge_with_config(2, input, &config).unwrap();
+
+        // Then
+        assert_eq!(
+            vec![
+                (Point::new(3, 1), vec![467, 35]),
+                (Point::new(6, 3), vec![633]),
+                (Point::new(3, 4), vec![617]),
+                (Point::new(5, 5), vec![592]),
+                (Point::new(5, 8), vec![755, 598]),
+            ],
+            gears
+                .iter()
+                .map(|gear| (gear.position, gear.numbers.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(467 + 35 + 633 + 617 + 592 + 755 + 598, sum);
+        assert_eq!("* (3,1) 467, 35: 502", gears[0].to_string());
+    }
+
+    #[test]
+    fn verify_gear_power_overflow() {
+        // Given
+        let input = vec!["99999*99999".to_string()];
+
+        // When
+        let result = challenge(2, input);
+
+        // Then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_sum_overflow() {
+        // Given
+        let parts = vec!["4294967295*".to_string(), "1*.........".to_string()];
+        let gears = vec![
+            "65535*65535".to_string(),
+            "...........".to_string(),
+            "65535*65535".to_string(),
+        ];
+
+        // When
+        let parts_result = challenge(1, parts);
+        let gears_result = challenge(2, gears);
+
+        // Then
+        assert_eq!(
+            "Overflow: Sum 4294967295 + 1 does not fit in u32",
+            parts_result.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Overflow: Sum 4294836225 + 4294836225 does not fit in u32",
+            gears_result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn verify_render() {
+        // Given
+        let binding = vec!["467..114..", "...*......", "..35..633."];
+        let input: Vec<String> = binding.iter().map(|line| line.to_string()).collect();
+
+        // When
+        let rendered = render(input.clone(), &Config::default()).unwrap();
+        let non_part = non_part_numbers(input, &Config::default()).unwrap();
+
+        // Then
+        let part = |digit: char| format!("{}{}{}", PART_COLOR, digit, RESET_COLOR);
+        let non_part_digit = |digit: char| format!("{}{}{}", NON_PART_COLOR, digit, RESET_COLOR);
+        assert_eq!(
+            format!(
+                "{}{}{}..{}{}{}..\n...{}{}{}......\n..{}{}..{}{}{}.\n",
+                part('4'),
+                part('6'),
+                part('7'),
+                non_part_digit('1'),
+                non_part_digit('1'),
+                non_part_digit('4'),
+                GEAR_COLOR,
+                '*',
+                RESET_COLOR,
+                part('3'),
+                part('5'),
+                non_part_digit('6'),
+                non_part_digit('3'),
+                non_part_digit('3'),
+            ),
+            rendered
+        );
+        assert_eq!(
+            vec![(Point::new(5, 0), 114), (Point::new(6, 2), 633)],
+            non_part
+        );
+    }
+
+    #[test]
+    fn verify_multi_byte_symbols_and_blank() {
+        // Given
+        let binding = vec!["12 €", "  5 ", "é 07"];
+        let input: Vec<String> = binding.iter().map(|line| line.to_string()).collect();
+        let config = Config {
+            blank: ' ',
+            ..Default::default()
+        };
+
+        // When
+        let (numbers, symbols) = parse_schematic(&Grid::parse(&input, Ok).unwrap(), ' ').unwrap();
+        let sum = challenge_with_config(1, input.clone(), &config).unwrap();
+        let rendered = render(input, &config).unwrap();
+
+        // Then
+        assert_eq!(3, numbers.len());
+        assert_eq!(
+            vec![(Point::new(3, 0), '€'), (Point::new(0, 2), 'é')],
+            symbols
+        );
+        assert_eq!(5, sum);
+        assert!(rendered.starts_with(&format!("{}1{}", NON_PART_COLOR, RESET_COLOR)));
+        assert!(rendered.ends_with(&format!(
+            "{}0{}{}7{}\n",
+            NON_PART_COLOR, RESET_COLOR, NON_PART_COLOR, RESET_COLOR
+        )));
+    }
+
+    #[test]
+    fn verify_number_overflow() {
+        // Given
+        let input = vec!["4294967295*".to_string(), "4294967296*".to_string()];
+
+        // When
+        let result = parse_schematic(&Grid::parse(&input, Ok).unwrap(), '.');
+
+        // Then
+        assert_eq!(
+            "Overflow: Number at (0,1) does not fit in u32",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn verify_ragged_lines() {
+        // Given
+        let input = vec!["467..".to_string(), "...*".to_string()];
+
+        // When
+        let result = challenge(1, input);
+
+        // Then
+        assert_eq!(
+            "Invalid input: Line 2 is 4 wide, expected 5",
+            result.unwrap_err().to_string()
+        );
+    }
 }
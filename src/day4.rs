@@ -1,15 +1,16 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
 
 use crate::Error;
 
-#[derive(Debug, Clone)]
-struct Card {
-    winning_numbers: Vec<u32>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    winning_numbers: HashSet<u32>,
     playing_numbers: Vec<u32>,
 }
 
 impl Card {
-    fn winning_count(&self) -> u32 {
+    pub fn winning_count(&self) -> u32 {
         let winning_count = self
             .playing_numbers
             .iter()
@@ -20,92 +21,218 @@ impl Card {
     }
 }
 
+fn parse_numbers(id: u32, kind: &str, numbers: &str) -> Result<Vec<u32>, Error> {
+    let numbers = numbers
+        .split_whitespace()
+        .map(|str_num| str_num.parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| Error::InvalidInput(format!("Failed to parse {} numbers: {}", kind, err)))?;
+
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = numbers.iter().find(|num| !seen.insert(**num)) {
+        return Err(Error::InvalidInput(format!(
+            "Card {} has duplicate {} number {}",
+            id, kind, duplicate
+        )));
+    }
+
+    Ok(numbers)
+}
+
 impl FromStr for Card {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_prefix, num_part) = s
+        let (prefix, num_part) = s
             .split_once(':')
             .ok_or(Error::InvalidInput("Missing ':' in Card input".to_string()))?;
 
+        let id = prefix
+            .trim()
+            .strip_prefix("Card")
+            .ok_or(Error::InvalidInput(format!("Missing 'Card' in {}", prefix)))?
+            .trim()
+            .parse::<u32>()
+            .map_err(|err| Error::InvalidInput(format!("Failed to parse card id: {}", err)))?;
+
         let (winning, playing) = num_part
             .split_once('|')
             .ok_or(Error::InvalidInput("Missing '|' in Card input".to_string()))?;
 
-        let winning_numbers = winning
-            .trim()
-            .split(' ')
-            .filter(|str_num| !str_num.is_empty())
-            .map(|str_num| str_num.parse::<u32>())
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|err| {
-                Error::InvalidInput(format!("Failed to parse winning numbers: {}", err))
-            })?;
-
-        let playing_numbers = playing
-            .trim()
-            .split(' ')
-            .filter(|str_num| !str_num.is_empty())
-            .map(|str_num| str_num.parse::<u32>())
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|err| {
-                Error::InvalidInput(format!("Failed to parse playing numbers: {}", err))
-            })?;
+        let winning_numbers = parse_numbers(id, "winning", winning)?.into_iter().collect();
+        let playing_numbers = parse_numbers(id, "playing", playing)?;
 
         Ok(Card {
+            id,
             playing_numbers,
             winning_numbers,
         })
     }
 }
 
-fn sum_winning_cards(input: Vec<String>) -> anyhow::Result<u32> {
-    let mut sum = 0;
-    for line in input {
-        let card: Card = line.parse()?;
-        let winning_count = card.winning_count();
-        if winning_count > 0 {
-            sum += 2_u32.pow(winning_count - 1)
+/// How many instances of a card are held once all copies are won, and which
+/// cards each of those instances wins a copy of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCopies {
+    pub id: u32,
+    pub copies: u64,
+    pub wins: Vec<u32>,
+}
+
+impl Display for CardCopies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Card {} x{}", self.id, self.copies)?;
+        if !self.wins.is_empty() {
+            let wins: Vec<String> = self.wins.iter().map(|id| id.to_string()).collect();
+            write!(f, " -> {}", wins.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_cards(input: Vec<String>) -> Result<Vec<Card>, Error> {
+    input.iter().map(|line| line.parse()).collect()
+}
+
+/// Cards must be numbered 1, 2, 3, ... in order, as copies are won by id.
+fn check_ids(cards: &[Card]) -> Result<(), Error> {
+    for (expected, card) in (1..).zip(cards) {
+        if card.id < expected {
+            return Err(Error::InvalidInput(format!(
+                "Card {} is duplicated or out of order",
+                card.id
+            )));
+        }
+        if card.id > expected {
+            return Err(Error::InvalidInput(format!(
+                "Card {} is missing before card {}",
+                expected, card.id
+            )));
         }
     }
 
-    Ok(sum)
+    Ok(())
 }
 
-fn count_winning_cards(input: Vec<String>) -> anyhow::Result<u32> {
-    let mut card_amount_map: HashMap<usize, u32> = HashMap::new();
-    let mut num_cards = 0;
-    for (i, line) in input.iter().enumerate() {
-        let card: Card = line.parse()?;
-        let winning_count = card.winning_count();
-        let card_count = card_amount_map.get(&i).unwrap_or(&0) + 1;
+/// How copies are won: each match wins a copy of one following card, at most
+/// `window` of them, and copies may reach `past_last` cards beyond the last
+/// card of the table. The puzzle has no window and stops at the last card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CopyRules {
+    pub window: Option<u32>,
+    pub past_last: u32,
+}
+
+pub fn copies(cards: &[Card], rules: &CopyRules) -> Result<Vec<CardCopies>, Error> {
+    check_ids(cards)?;
 
-        card_amount_map.insert(i, card_count);
+    let last_id = cards.last().map_or(0, |card| card.id);
+    let reach = last_id
+        .checked_add(rules.past_last)
+        .ok_or_else(|| Error::Overflow(format!("Cards past {} do not fit in u32", last_id)))?;
+    let mut copies: Vec<CardCopies> = cards
+        .iter()
+        .map(|card| CardCopies {
+            id: card.id,
+            copies: 1,
+            wins: Vec::new(),
+        })
+        .collect();
 
-        for winning_offset in 1..winning_count + 1 {
-            let index_to_update = i + winning_offset as usize;
-            let current_count = card_amount_map.get(&index_to_update).unwrap_or(&0);
+    for card in cards {
+        let index = |id: u32| (id - 1) as usize;
+        let card_count = copies[index(card.id)].copies;
+        let won_count = rules.window.map_or(card.winning_count(), |window| {
+            card.winning_count().min(window)
+        });
+        let last_won = card.id.saturating_add(won_count).min(reach);
 
-            card_amount_map.insert(index_to_update, current_count + card_count);
+        for won_id in card.id + 1..=last_won {
+            // Cards past the last one are only tracked once a copy reaches them.
+            if index(won_id) == copies.len() {
+                copies.push(CardCopies {
+                    id: won_id,
+                    copies: 0,
+                    wins: Vec::new(),
+                });
+            }
+            let won = &mut copies[index(won_id)];
+            won.copies = won.copies.checked_add(card_count).ok_or_else(|| {
+                Error::Overflow(format!("Copies of card {} do not fit in u64", won_id))
+            })?;
+            copies[index(card.id)].wins.push(won_id);
         }
-        num_cards += 1;
     }
 
-    Ok(card_amount_map
-        .iter()
-        .filter(|(i, _)| **i < num_cards as usize)
-        .map(|(_, v)| v)
-        .sum())
+    Ok(copies)
 }
 
-pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<u32> {
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Rule {
+    /// One point for the first match, doubled for every further match.
+    Doubling,
+    /// One point per match.
+    Linear,
+    /// The number of cards held once every copy is won.
+    CopiesWon(CopyRules),
+}
+
+impl Rule {
+    pub fn score(&self, cards: &[Card]) -> Result<u64, Error> {
+        match self {
+            Self::Doubling => cards.iter().try_fold(0_u64, |sum, card| {
+                match card.winning_count() {
+                    0 => Some(sum),
+                    count => 1_u64
+                        .checked_shl(count - 1)
+                        .and_then(|score| sum.checked_add(score)),
+                }
+                .ok_or_else(|| {
+                    Error::Overflow(format!("Score of card {} does not fit in u64", card.id))
+                })
+            }),
+            Self::Linear => Ok(cards
+                .iter()
+                .map(|card| u64::from(card.winning_count()))
+                .sum()),
+            Self::CopiesWon(rules) => copies(cards, rules)?
+                .iter()
+                .try_fold(0_u64, |sum, card| sum.checked_add(card.copies))
+                .ok_or_else(|| Error::Overflow("Number of cards does not fit in u64".to_string())),
+        }
+    }
+}
+
+/// `doubling`, `linear` or `copies`, the latter with the puzzle copy rules.
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "doubling" => Ok(Self::Doubling),
+            "linear" => Ok(Self::Linear),
+            "copies" => Ok(Self::CopiesWon(CopyRules::default())),
+            other => Err(Error::InvalidInput(format!(
+                "{} is not a scratchcard scoring rule",
+                other
+            ))),
+        }
+    }
+}
+
+pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<u64> {
     match part {
-        1 => sum_winning_cards(input),
-        2 => count_winning_cards(input),
+        1 => challenge_with_rule(input, &Rule::Doubling),
+        2 => challenge_with_rule(input, &Rule::CopiesWon(CopyRules::default())),
         _ => Ok(0),
     }
 }
 
+pub fn challenge_with_rule(input: Vec<String>, rule: &Rule) -> anyhow::Result<u64> {
+    Ok(rule.score(&parse_cards(input)?)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +276,185 @@ mod tests {
         // Then
         assert_eq!(30, sum);
     }
+
+    #[test]
+    fn verify_parse_card() {
+        // Given
+        let line = "Card  12: 41 48 83 | 83 86  6 48";
+
+        // When
+        let card: Card = line.parse().unwrap();
+
+        // Then
+        assert_eq!(12, card.id);
+        assert_eq!(HashSet::from([41, 48, 83]), card.winning_numbers);
+        assert_eq!(2, card.winning_count());
+    }
+
+    #[test]
+    fn verify_duplicate_numbers() {
+        // Given
+        let winning = "Card 3: 41 48 41 | 83 86";
+        let playing = "Card 4: 41 48 | 83 86 83";
+
+        // When
+        let winning_err = winning.parse::<Card>().unwrap_err();
+        let playing_err = playing.parse::<Card>().unwrap_err();
+
+        // Then
+        assert_eq!(
+            "Invalid input: Card 3 has duplicate winning number 41",
+            winning_err.to_string()
+        );
+        assert_eq!(
+            "Invalid input: Card 4 has duplicate playing number 83",
+            playing_err.to_string()
+        );
+    }
+
+    #[test]
+    fn verify_copies_trace() {
+        // Given
+        let binding = vec![
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let input = binding.iter().map(|line| line.to_string()).collect();
+
+        // When
+        let copies = copies(&parse_cards(input).unwrap(), &CopyRules::default()).unwrap();
+
+        // Then
+        let counts: Vec<u64> = copies.iter().map(|card| card.copies).collect();
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], counts);
+        assert_eq!(vec![2, 3, 4, 5], copies[0].wins);
+        assert_eq!("Card 3 x4 -> 4, 5", copies[2].to_string());
+        assert_eq!("Card 6 x1", copies[5].to_string());
+    }
+
+    #[test]
+    fn verify_card_ids() {
+        // Given
+        let cards = |ids: &[u32]| -> Vec<Card> {
+            ids.iter()
+                .map(|id| format!("Card {}: 1 | 1", id).parse().unwrap())
+                .collect()
+        };
+
+        // When
+        let gap = copies(&cards(&[1, 2, 4]), &CopyRules::default()).unwrap_err();
+        let duplicate = copies(&cards(&[1, 2, 2]), &CopyRules::default()).unwrap_err();
+        let late_start = copies(&cards(&[2, 3]), &CopyRules::default()).unwrap_err();
+
+        // Then
+        assert_eq!(
+            "Invalid input: Card 3 is missing before card 4",
+            gap.to_string()
+        );
+        assert_eq!(
+            "Invalid input: Card 2 is duplicated or out of order",
+            duplicate.to_string()
+        );
+        assert_eq!(
+            "Invalid input: Card 1 is missing before card 2",
+            late_start.to_string()
+        );
+    }
+
+    #[test]
+    fn verify_wide_counts() {
+        // Given
+        let numbers: Vec<String> = (1..=40).map(|num| num.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let input: Vec<String> = (1..=41)
+            .map(|id| format!("Card {}: {} | {}", id, numbers, numbers))
+            .collect();
+        let too_many: Vec<String> = (1..=65).map(|num| num.to_string()).collect();
+        let too_many = too_many.join(" ");
+
+        // When
+        let score = challenge(1, input.clone()).unwrap();
+        let count = challenge(2, input).unwrap();
+        let overflow = challenge(1, vec![format!("Card 1: {} | {}", too_many, too_many)]);
+
+        // Then
+        assert_eq!(41 * (1 << 39), score);
+        assert_eq!((1 << 41) - 1, count);
+        assert_eq!(
+            "Overflow: Score of card 1 does not fit in u64",
+            overflow.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn verify_rules() {
+        // Given
+        let binding = vec![
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let cards = parse_cards(binding.iter().map(|line| line.to_string()).collect()).unwrap();
+        let windowed = Rule::CopiesWon(CopyRules {
+            window: Some(1),
+            past_last: 0,
+        });
+        let past_last = CopyRules {
+            window: None,
+            past_last: 2,
+        };
+
+        // When
+        let linear = Rule::Linear.score(&cards).unwrap();
+        let windowed = windowed.score(&cards).unwrap();
+        let past_last_score = Rule::CopiesWon(past_last).score(&cards[..4]).unwrap();
+        let past_last_copies = copies(&cards[..4], &past_last).unwrap();
+
+        // Then
+        assert_eq!(4 + 2 + 2 + 1, linear);
+        assert_eq!(1 + 2 + 3 + 4 + 5 + 1, windowed);
+        assert_eq!(1 + 2 + 4 + 8 + 13, past_last_score);
+        assert_eq!("Card 5 x13", past_last_copies[4].to_string());
+        assert_eq!(5, past_last_copies.len());
+        assert_eq!(Rule::Doubling, "doubling".parse().unwrap());
+        assert!("tripling".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn verify_large_past_last() {
+        // Given
+        let cards = parse_cards(vec!["Card 1: 1 | 1".to_string()]).unwrap();
+        let far = CopyRules {
+            window: None,
+            past_last: 4_000_000_000,
+        };
+        let too_far = CopyRules {
+            window: None,
+            past_last: u32::MAX,
+        };
+
+        // When
+        let far_copies = copies(&cards, &far).unwrap();
+        let too_far_copies = copies(&cards, &too_far);
+
+        // Then
+        assert_eq!(
+            vec!["Card 1 x1 -> 2", "Card 2 x1"],
+            far_copies
+                .iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "Overflow: Cards past 1 do not fit in u32",
+            too_far_copies.unwrap_err().to_string()
+        );
+    }
 }
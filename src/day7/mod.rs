@@ -1,11 +1,13 @@
-mod part1;
-mod part2;
+pub mod poker;
+mod rules;
 
-use std::{cmp::Ordering, str::FromStr};
+pub use rules::{Rules, WildRank};
+
+use std::{fmt::Display, str::FromStr};
 
 use crate::Error;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum Card {
     Two,
     Three,
@@ -48,12 +50,56 @@ impl TryFrom<char> for Card {
     }
 }
 
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Card::Two => '2',
+            Card::Three => '3',
+            Card::Four => '4',
+            Card::Five => '5',
+            Card::Six => '6',
+            Card::Seven => '7',
+            Card::Eight => '8',
+            Card::Nine => '9',
+            Card::T => 'T',
+            Card::J => 'J',
+            Card::Q => 'Q',
+            Card::K => 'K',
+            Card::A => 'A',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+fn cards_to_string(cards: &[Card]) -> String {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
 #[derive(PartialEq, Eq)]
 pub struct Hand {
     cards: Vec<Card>,
     bid: u32,
 }
 
+/// Orders hands by type, then by the rank of each card in turn. The ranks are
+/// packed four bits per card so the key is `Copy` and cheap to compare.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct HandKey {
+    hand_type: HandType,
+    ranks: u64,
+}
+
+impl Hand {
+    /// How many of each card the hand holds, indexed by `Card as usize`.
+    fn card_counts(&self) -> [u8; 13] {
+        let mut counts = [0; 13];
+        for card in self.cards.iter() {
+            counts[*card as usize] += 1;
+        }
+        counts
+    }
+}
+
 impl FromStr for Hand {
     type Err = Error;
 
@@ -68,13 +114,6 @@ impl FromStr for Hand {
             .map(|c| c.try_into())
             .collect::<Result<Vec<Card>, _>>()?;
 
-        if cards.len() != 5 {
-            return Err(Error::InvalidInput(format!(
-                "Invalid amount of cards, expected 5 got: {}",
-                cards.len()
-            )));
-        }
-
         Ok(Hand {
             cards,
             bid: bid_str
@@ -84,7 +123,7 @@ impl FromStr for Hand {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum HandType {
     HighCard,
     OnePair,
@@ -95,67 +134,139 @@ pub enum HandType {
     FiveOfAKind,
 }
 
+impl HandType {
+    /// Classifies a hand of any size by its two largest groups of equal
+    /// cards, so six of a kind counts as five of a kind and three pairs as two
+    /// pair.
+    fn from_counts(counts: &[u8; 13]) -> Self {
+        let mut pattern = *counts;
+        pattern.sort_unstable_by(|a, b| b.cmp(a));
+
+        match (pattern[0], pattern[1]) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// Each card takes four bits of a `u64` sort key.
+const MAX_HAND_SIZE: usize = 16;
+
 fn parse_hands<T: Iterator<Item = Result<String, std::io::Error>>>(
     input: T,
+    rules: &Rules,
 ) -> anyhow::Result<Vec<Hand>> {
+    if rules.hand_size > MAX_HAND_SIZE {
+        return Err(Error::InvalidInput(format!(
+            "Hands of {} cards are too large, at most {} are supported",
+            rules.hand_size, MAX_HAND_SIZE
+        ))
+        .into());
+    }
+
     let mut hands: Vec<Hand> = Vec::new();
     for line in input {
-        hands.push(line?.parse()?);
+        let hand: Hand = line?.parse()?;
+        if hand.cards.len() != rules.hand_size {
+            return Err(Error::InvalidInput(format!(
+                "Invalid amount of cards, expected {} got: {}",
+                rules.hand_size,
+                hand.cards.len()
+            ))
+            .into());
+        }
+        hands.push(hand);
     }
     Ok(hands)
 }
 
-fn cmp_hands<FType: Fn(&Hand) -> HandType, FCmp: Fn(&Card, &Card) -> Ordering>(
-    a: &Hand,
-    b: &Hand,
-    hand_type: &FType,
-    cmp_cards: &FCmp,
-) -> Ordering {
-    if a == b {
-        return Ordering::Equal;
-    }
+/// The hands from weakest to strongest.
+fn rank_hands<'a>(hands: &'a [Hand], rules: &Rules) -> Vec<&'a Hand> {
+    let mut keyed: Vec<(HandKey, &Hand)> =
+        hands.iter().map(|hand| (rules.key(hand), hand)).collect();
+    keyed.sort_by_key(|(key, _)| *key);
 
-    let a_hand_type = hand_type(a);
-    let b_hand_type = hand_type(b);
+    keyed.into_iter().map(|(_, hand)| hand).collect()
+}
 
-    if a_hand_type != b_hand_type {
-        return a_hand_type.cmp(&b_hand_type);
+fn total_winnings(hands: &[Hand], rules: &Rules) -> u32 {
+    let mut sum: u32 = 0;
+    for (i, hand) in rank_hands(hands, rules).iter().enumerate() {
+        sum += hand.bid * (i as u32 + 1);
     }
 
-    for (a_card, b_card) in a.cards.iter().zip(b.cards.iter()) {
-        match cmp_cards(a_card, b_card) {
-            Ordering::Equal => {}
-            ordering => return ordering,
-        }
-    }
+    sum
+}
 
-    Ordering::Equal
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Explanation {
+    pub rank: u32,
+    pub cards: Vec<Card>,
+    pub bid: u32,
+    pub hand_type: HandType,
+    /// The cards the wild cards stand in for, when the hand holds any.
+    pub substitution: Option<Vec<Card>>,
 }
 
-fn total_winnings<FType: Fn(&Hand) -> HandType, FCmp: Fn(&Card, &Card) -> Ordering>(
-    hands: &mut Vec<Hand>,
-    hand_type: FType,
-    cmp_cards: FCmp,
-) -> u32 {
-    hands.sort_by(|a, b| cmp_hands(a, b, &hand_type, &cmp_cards));
+/// `5 QQQJA 483 FourOfAKind as QQQQA`
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {:?}",
+            self.rank,
+            cards_to_string(&self.cards),
+            self.bid,
+            self.hand_type
+        )?;
+        if let Some(substitution) = &self.substitution {
+            write!(f, " as {}", cards_to_string(substitution))?;
+        }
 
-    let mut sum: u32 = 0;
-    for (i, hand) in hands.iter().enumerate() {
-        sum += hand.bid * (i as u32 + 1);
+        Ok(())
     }
+}
 
-    sum
+pub fn explain<T: Iterator<Item = Result<String, std::io::Error>>>(
+    input: &mut T,
+    rules: &Rules,
+) -> anyhow::Result<Vec<Explanation>> {
+    let hands = parse_hands(input, rules)?;
+
+    Ok(rank_hands(&hands, rules)
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| Explanation {
+            rank: i as u32 + 1,
+            cards: hand.cards.clone(),
+            bid: hand.bid,
+            hand_type: rules.hand_type(hand),
+            substitution: rules.substitution(hand),
+        })
+        .collect())
 }
 
 pub fn challenge<T: Iterator<Item = Result<String, std::io::Error>>>(
     part: u32,
     input: &mut T,
 ) -> anyhow::Result<u32> {
-    Ok(match part {
-        1 => total_winnings(&mut parse_hands(input)?, part1::hand_type, part1::cmp_cards),
-        2 => total_winnings(&mut parse_hands(input)?, part2::hand_type, part2::cmp_cards),
-        _ => 0,
-    })
+    match part {
+        1 => challenge_with_rules(input, &Rules::standard()),
+        2 => challenge_with_rules(input, &Rules::jokers()),
+        _ => Ok(0),
+    }
+}
+
+pub fn challenge_with_rules<T: Iterator<Item = Result<String, std::io::Error>>>(
+    input: &mut T,
+    rules: &Rules,
+) -> anyhow::Result<u32> {
+    Ok(total_winnings(&parse_hands(input, rules)?, rules))
 }
 
 #[cfg(test)]
@@ -231,4 +342,183 @@ mod tests {
         // Then
         assert_eq!(2 + 4, total);
     }
+
+    #[test]
+    fn verify_rule_sets() {
+        // Given
+        let binding = vec!["2345A 1", "KKQQ2 10", "AAKK3 100"];
+        let jokers_high = Rules {
+            wild_rank: WildRank::Highest,
+            ..Rules::jokers()
+        };
+        let aces_low = Rules {
+            order: Rules::parse_order("A23456789TJQK").unwrap(),
+            ..Rules::standard()
+        };
+
+        // When
+        let deuces = challenge_with_rules(&mut to_input(&binding), &Rules::deuces()).unwrap();
+        let jokers_high =
+            challenge_with_rules(&mut to_input(&vec!["JJ234 1", "AA234 10"]), &jokers_high)
+                .unwrap();
+        let aces_low =
+            challenge_with_rules(&mut to_input(&vec!["A2345 1", "23456 10"]), &aces_low).unwrap();
+
+        // Then
+        assert_eq!(1 + 100 * 2 + 10 * 3, deuces);
+        assert_eq!(10 + 2, jokers_high);
+        assert_eq!(1 + 10 * 2, aces_low);
+    }
+
+    #[test]
+    fn verify_rule_errors() {
+        // Given
+        let four_cards = Rules {
+            hand_size: 4,
+            ..Rules::standard()
+        };
+
+        // When
+        let wrong_size = challenge_with_rules(&mut to_input(&vec!["23456 1"]), &four_cards);
+
+        // Then
+        assert_eq!(
+            "Invalid input: Invalid amount of cards, expected 4 got: 5",
+            wrong_size.unwrap_err().to_string()
+        );
+        assert!(Rules::parse_order("23456789TJQKK").is_err());
+        assert!(Rules::parse_order("23456789TJQK").is_err());
+        assert!("poker".parse::<Rules>().is_err());
+        assert_eq!(WildRank::Highest, "highest".parse().unwrap());
+    }
+
+    #[test]
+    fn verify_explain() {
+        // Given
+        let binding = vec!["J3456 1", "T345J 4", "JJJJJ 2"];
+
+        // When
+        let standard = explain(&mut to_input(&binding), &Rules::standard()).unwrap();
+        let jokers = explain(&mut to_input(&binding), &Rules::jokers()).unwrap();
+
+        // Then
+        let lines = |explanations: &Vec<Explanation>| -> Vec<String> {
+            explanations.iter().map(|it| it.to_string()).collect()
+        };
+        assert_eq!(
+            vec![
+                "1 T345J 4 HighCard",
+                "2 J3456 1 HighCard",
+                "3 JJJJJ 2 FiveOfAKind"
+            ],
+            lines(&standard)
+        );
+        assert_eq!(
+            vec![
+                "1 J3456 1 OnePair as 63456",
+                "2 T345J 4 OnePair as T345T",
+                "3 JJJJJ 2 FiveOfAKind as AAAAA"
+            ],
+            lines(&jokers)
+        );
+    }
+
+    const ALL_CARDS: [Card; 13] = [
+        Card::Two,
+        Card::Three,
+        Card::Four,
+        Card::Five,
+        Card::Six,
+        Card::Seven,
+        Card::Eight,
+        Card::Nine,
+        Card::T,
+        Card::J,
+        Card::Q,
+        Card::K,
+        Card::A,
+    ];
+
+    /// Every multiset of `size` cards drawn from `cards`, each sorted.
+    fn multisets(size: usize, cards: &[Card]) -> Vec<Vec<Card>> {
+        if size == 0 {
+            return vec![Vec::new()];
+        }
+
+        let mut result = Vec::new();
+        for (i, card) in cards.iter().enumerate() {
+            for mut rest in multisets(size - 1, &cards[i..]) {
+                rest.insert(0, *card);
+                result.push(rest);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn verify_joker_promotion_is_optimal() {
+        let standard = Rules::standard();
+        let jokers = Rules::jokers();
+        let others: Vec<Card> = ALL_CARDS
+            .iter()
+            .filter(|card| **card != Card::J)
+            .copied()
+            .collect();
+
+        for size in 1..=6 {
+            for cards in multisets(size, &ALL_CARDS) {
+                // Given
+                let kept: Vec<Card> = cards.iter().filter(|c| **c != Card::J).copied().collect();
+                let hand = Hand { cards, bid: 0 };
+
+                // When
+                let hand_type = jokers.hand_type(&hand);
+                let best = multisets(size - kept.len(), &others)
+                    .into_iter()
+                    .map(|replacement| {
+                        let cards = kept.iter().chain(replacement.iter()).copied().collect();
+                        standard.hand_type(&Hand { cards, bid: 0 })
+                    })
+                    .max()
+                    .unwrap();
+                let substituted = jokers
+                    .substitution(&hand)
+                    .map(|cards| standard.hand_type(&Hand { cards, bid: 0 }));
+
+                // Then
+                assert_eq!(best, hand_type, "{}", cards_to_string(&hand.cards));
+                assert_eq!(
+                    hand_type,
+                    substituted.unwrap_or(hand_type),
+                    "{}",
+                    cards_to_string(&hand.cards)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verify_hand_sizes() {
+        // Given
+        let binding = vec!["AAAAAA 1", "KKKQQQ 10", "222334 100", "JJ2345 1000"];
+        let six_cards = Rules {
+            hand_size: 6,
+            ..Rules::jokers()
+        };
+        let too_large = Rules {
+            hand_size: 17,
+            ..Rules::standard()
+        };
+
+        // When
+        let total = challenge_with_rules(&mut to_input(&binding), &six_cards).unwrap();
+        let error = challenge_with_rules(&mut to_input(&binding), &too_large);
+
+        // Then
+        assert_eq!(1000 + 100 * 2 + 10 * 3 + 4, total);
+        assert_eq!(
+            "Invalid input: Hands of 17 cards are too large, at most 16 are supported",
+            error.unwrap_err().to_string()
+        );
+    }
 }
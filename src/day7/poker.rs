@@ -0,0 +1,293 @@
+use std::{cmp::Reverse, collections::HashSet, fmt::Display, str::FromStr};
+
+use super::Card;
+use crate::Error;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl TryFrom<char> for Suit {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'c' => Ok(Suit::Clubs),
+            'd' => Ok(Suit::Diamonds),
+            'h' => Ok(Suit::Hearts),
+            's' => Ok(Suit::Spades),
+            _ => Err(Error::InvalidInput(format!(
+                "{} is not a valid suit",
+                value
+            ))),
+        }
+    }
+}
+
+impl Display for Suit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PokerCard {
+    pub rank: Card,
+    pub suit: Suit,
+}
+
+/// A rank followed by a suit, like `Ah` or `Td`.
+impl FromStr for PokerCard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(rank), Some(suit), None) => Ok(PokerCard {
+                rank: rank.try_into()?,
+                suit: suit.try_into()?,
+            }),
+            _ => Err(Error::InvalidInput(format!("{} is not a valid card", s))),
+        }
+    }
+}
+
+impl Display for PokerCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// Parses whitespace separated cards, like `Ah Kd 7c 7s 2h`.
+pub fn parse_cards(s: &str) -> Result<Vec<PokerCard>, Error> {
+    s.split_whitespace().map(|card| card.parse()).collect()
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum PokerHandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+/// Compares as poker hands do: by type, then by the ranks that break ties in
+/// order of importance, e.g. the pair before the kickers.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Evaluation {
+    pub hand_type: PokerHandType,
+    pub tie_breakers: Vec<Card>,
+}
+
+/// `OnePair 7 A K 2`
+impl Display for Evaluation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.hand_type)?;
+        for rank in self.tie_breakers.iter() {
+            write!(f, " {}", rank)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_distinct(cards: &[PokerCard]) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    match cards.iter().find(|card| !seen.insert(**card)) {
+        Some(duplicate) => Err(Error::InvalidInput(format!(
+            "{} appears more than once",
+            duplicate
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The highest rank of a straight made of these ranks, sorted from highest to
+/// lowest, with the ace playing low in `5 4 3 2 A`.
+fn straight_high(ranks: &[Card]) -> Option<Card> {
+    let is_run = ranks
+        .windows(2)
+        .all(|pair| pair[0] as usize == pair[1] as usize + 1);
+
+    match ranks {
+        _ if is_run => Some(ranks[0]),
+        [Card::A, Card::Five, Card::Four, Card::Three, Card::Two] => Some(Card::Five),
+        _ => None,
+    }
+}
+
+pub fn evaluate(cards: &[PokerCard]) -> Result<Evaluation, Error> {
+    if cards.len() != 5 {
+        return Err(Error::InvalidInput(format!(
+            "Invalid amount of cards, expected 5 got: {}",
+            cards.len()
+        )));
+    }
+    check_distinct(cards)?;
+
+    // Groups of equal ranks, largest first and the higher rank on a tie.
+    let mut groups: Vec<(usize, Card)> = Vec::new();
+    for card in cards {
+        match groups.iter_mut().find(|(_, rank)| *rank == card.rank) {
+            Some((count, _)) => *count += 1,
+            None => groups.push((1, card.rank)),
+        }
+    }
+    groups.sort_by_key(|group| Reverse(*group));
+    let counts: Vec<usize> = groups.iter().map(|(count, _)| *count).collect();
+    let ranks: Vec<Card> = groups.iter().map(|(_, rank)| *rank).collect();
+
+    let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+    let straight = if groups.len() == 5 {
+        straight_high(&ranks)
+    } else {
+        None
+    };
+
+    let hand_type = match (straight, is_flush, counts.as_slice()) {
+        (Some(_), true, _) => PokerHandType::StraightFlush,
+        (_, _, [4, ..]) => PokerHandType::FourOfAKind,
+        (_, _, [3, 2]) => PokerHandType::FullHouse,
+        (_, true, _) => PokerHandType::Flush,
+        (Some(_), _, _) => PokerHandType::Straight,
+        (_, _, [3, ..]) => PokerHandType::ThreeOfAKind,
+        (_, _, [2, 2, ..]) => PokerHandType::TwoPair,
+        (_, _, [2, ..]) => PokerHandType::OnePair,
+        _ => PokerHandType::HighCard,
+    };
+
+    Ok(Evaluation {
+        hand_type,
+        tie_breakers: match straight {
+            Some(high) => vec![high],
+            None => ranks,
+        },
+    })
+}
+
+/// The best five card hand out of five or more cards, as in seven card stud
+/// or Texas hold'em.
+pub fn best_hand(cards: &[PokerCard]) -> Result<(Evaluation, Vec<PokerCard>), Error> {
+    if cards.len() < 5 {
+        return Err(Error::InvalidInput(format!(
+            "Invalid amount of cards, expected at least 5 got: {}",
+            cards.len()
+        )));
+    }
+    check_distinct(cards)?;
+
+    let mut best: Option<(Evaluation, Vec<PokerCard>)> = None;
+    let mut indices = [0, 1, 2, 3, 4];
+    loop {
+        let hand: Vec<PokerCard> = indices.iter().map(|i| cards[*i]).collect();
+        let evaluation = evaluate(&hand)?;
+        if best.as_ref().is_none_or(|(best, _)| evaluation > *best) {
+            best = Some((evaluation, hand));
+        }
+
+        // Advance to the next combination in lexicographic order.
+        let Some(i) = (0..5).rev().find(|i| indices[*i] < cards.len() - 5 + i) else {
+            break;
+        };
+        indices[i] += 1;
+        for j in i + 1..5 {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+
+    best.ok_or_else(|| Error::InvalidInput("No hand to evaluate".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(hand: &str) -> Evaluation {
+        evaluate(&parse_cards(hand).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn verify_hand_types() {
+        // Given
+        let hands = [
+            ("Ah Kd 9c 5s 2h", PokerHandType::HighCard),
+            ("7h 7d Ac Ks 2h", PokerHandType::OnePair),
+            ("7h 7d Kc Ks 2h", PokerHandType::TwoPair),
+            ("7h 7d 7c Ks 2h", PokerHandType::ThreeOfAKind),
+            ("5h 4d 3c 2s Ah", PokerHandType::Straight),
+            ("Th Jd Qc Ks Ah", PokerHandType::Straight),
+            ("Ah Kh 9h 5h 2h", PokerHandType::Flush),
+            ("7h 7d 7c Ks Kh", PokerHandType::FullHouse),
+            ("7h 7d 7c 7s Kh", PokerHandType::FourOfAKind),
+            ("9s Ts Js Qs Ks", PokerHandType::StraightFlush),
+        ];
+
+        for (hand, expected) in hands {
+            // When
+            let evaluation = eval(hand);
+
+            // Then
+            assert_eq!(expected, evaluation.hand_type, "{}", hand);
+        }
+    }
+
+    #[test]
+    fn verify_tie_breaks() {
+        // Then
+        assert!(eval("7h 7d Ac Ks 2h") > eval("7s 7c Ad Qs Jh"));
+        assert!(eval("7h 7d Kc Ks 2h") < eval("7s 7c Kd Ks 3h"));
+        assert!(eval("5h 4d 3c 2s Ah") < eval("6h 5d 4c 3s 2h"));
+        assert!(eval("Ah Kh 9h 5h 2h") > eval("Ks Qs Js 9s 7s"));
+        assert!(eval("2h 2d 2c As Ah") < eval("3h 3d 3c 4s 4h"));
+        assert_eq!(eval("Ah Kd 9c 5s 2h"), eval("As Kc 9d 5h 2c"));
+        assert_eq!("OnePair 7 A K 2", eval("7h 7d Ac Ks 2h").to_string());
+        assert_eq!("Straight 5", eval("5h 4d 3c 2s Ah").to_string());
+    }
+
+    #[test]
+    fn verify_best_hand() {
+        // Given
+        let cards = parse_cards("9h Th Jc Qd Kh 2h 5h").unwrap();
+
+        // When
+        let (evaluation, hand) = best_hand(&cards).unwrap();
+
+        // Then
+        assert_eq!(PokerHandType::Flush, evaluation.hand_type);
+        assert_eq!(parse_cards("9h Th Kh 2h 5h").unwrap(), hand);
+    }
+
+    #[test]
+    fn verify_invalid_cards() {
+        // When
+        let duplicate = evaluate(&parse_cards("Ah Ah 9c 5s 2h").unwrap());
+        let too_few = best_hand(&parse_cards("Ah Kd 9c 5s").unwrap());
+
+        // Then
+        assert_eq!(
+            "Invalid input: Ah appears more than once",
+            duplicate.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Invalid input: Invalid amount of cards, expected at least 5 got: 4",
+            too_few.unwrap_err().to_string()
+        );
+        assert!(parse_cards("Ax").is_err());
+        assert!(parse_cards("10h").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::HashSet, str::FromStr};
+
+use super::{Card, Hand, HandKey, HandType};
+use crate::Error;
+
+/// Where wild cards rank when comparing hands of the same type card by card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WildRank {
+    /// Below every other card, like the puzzle jokers.
+    Lowest,
+    /// At their usual place in the card order.
+    Natural,
+    /// Above every other card.
+    Highest,
+}
+
+/// `lowest`, `natural` or `highest`.
+impl FromStr for WildRank {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lowest" => Ok(Self::Lowest),
+            "natural" => Ok(Self::Natural),
+            "highest" => Ok(Self::Highest),
+            other => Err(Error::InvalidInput(format!(
+                "{} is not a wild card rank",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rules {
+    /// Every card once, from weakest to strongest.
+    pub order: Vec<Card>,
+    /// Cards that stand in for whichever card makes the best hand.
+    pub wilds: Vec<Card>,
+    pub wild_rank: WildRank,
+    pub hand_size: usize,
+}
+
+impl Rules {
+    /// The part 1 rules: no wild cards, aces high.
+    pub fn standard() -> Self {
+        Rules {
+            order: vec![
+                Card::Two,
+                Card::Three,
+                Card::Four,
+                Card::Five,
+                Card::Six,
+                Card::Seven,
+                Card::Eight,
+                Card::Nine,
+                Card::T,
+                Card::J,
+                Card::Q,
+                Card::K,
+                Card::A,
+            ],
+            wilds: Vec::new(),
+            wild_rank: WildRank::Natural,
+            hand_size: 5,
+        }
+    }
+
+    /// The part 2 rules: `J` is a joker which ranks lowest in tie-breaks.
+    pub fn jokers() -> Self {
+        Rules {
+            wilds: vec![Card::J],
+            wild_rank: WildRank::Lowest,
+            ..Self::standard()
+        }
+    }
+
+    /// Deuces wild, ranking as twos in tie-breaks.
+    pub fn deuces() -> Self {
+        Rules {
+            wilds: vec![Card::Two],
+            ..Self::standard()
+        }
+    }
+
+    /// Parses a card order such as `23456789TJQKA`, weakest first.
+    pub fn parse_order(s: &str) -> Result<Vec<Card>, Error> {
+        let order = s
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<Card>, _>>()?;
+
+        if order.len() != 13 || order.iter().collect::<HashSet<_>>().len() != 13 {
+            return Err(Error::InvalidInput(format!(
+                "{} does not list every card exactly once",
+                s
+            )));
+        }
+
+        Ok(order)
+    }
+
+    fn is_wild(&self, card: &Card) -> bool {
+        self.wilds.contains(card)
+    }
+
+    pub fn hand_type(&self, hand: &Hand) -> HandType {
+        let mut counts = hand.card_counts();
+        let mut wild_count = 0;
+        for wild in self.wilds.iter() {
+            wild_count += std::mem::take(&mut counts[*wild as usize]);
+        }
+        if let Some(highest) = counts.iter_mut().max() {
+            *highest += wild_count;
+        }
+
+        HandType::from_counts(&counts)
+    }
+
+    /// The hand with every wild card replaced by the card it stands in for,
+    /// or `None` when the hand holds no wild card. Wilds join the most common
+    /// card, the strongest one on a tie, which always gives the best type.
+    pub fn substitution(&self, hand: &Hand) -> Option<Vec<Card>> {
+        if !hand.cards.iter().any(|card| self.is_wild(card)) {
+            return None;
+        }
+
+        let counts = hand.card_counts();
+        let target = self
+            .order
+            .iter()
+            .filter(|card| !self.is_wild(card))
+            .max_by_key(|card| counts[**card as usize])?;
+
+        Some(
+            hand.cards
+                .iter()
+                .map(|card| if self.is_wild(card) { *target } else { *card })
+                .collect(),
+        )
+    }
+
+    /// Ranks start at 1 so lowest wilds can take 0, and stay below 16 so they
+    /// pack into four bits.
+    pub fn card_rank(&self, card: &Card) -> u8 {
+        match (self.is_wild(card), self.wild_rank) {
+            (true, WildRank::Lowest) => 0,
+            (true, WildRank::Highest) => 14,
+            _ => self
+                .order
+                .iter()
+                .position(|it| it == card)
+                .map_or(0, |position| position as u8 + 1),
+        }
+    }
+
+    pub fn key(&self, hand: &Hand) -> HandKey {
+        HandKey {
+            hand_type: self.hand_type(hand),
+            ranks: hand.cards.iter().fold(0, |ranks, card| {
+                ranks << 4 | u64::from(self.card_rank(card))
+            }),
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// `standard`, `jokers` or `deuces`.
+impl FromStr for Rules {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Self::standard()),
+            "jokers" => Ok(Self::jokers()),
+            "deuces" => Ok(Self::deuces()),
+            other => Err(Error::InvalidInput(format!(
+                "{} is not a camel cards rule set",
+                other
+            ))),
+        }
+    }
+}
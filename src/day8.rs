@@ -1,6 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use crate::Error;
+use crate::{math::number::lcm_all, Error};
 
 type DesertMap = HashMap<String, (String, String)>;
 
@@ -70,68 +70,17 @@ fn find_sleep(start: String, directions: &String, map: &DesertMap) -> anyhow::Re
     Ok(steps)
 }
 
-fn is_prime(n: u64) -> bool {
-    if n == 2 || n == 3 {
-        return true;
-    }
-
-    if n <= 1 || n % 2 == 0 || n % 3 == 0 {
-        return false;
-    }
-
-    for i in (5..).step_by(6).take_while(|i| i * i <= n) {
-        if n % i == 0 || n % (i + 2) == 0 {
-            return false;
-        }
-    }
-
-    true
-}
-
-fn is_factor(number: u64, maybe_factor: u64) -> Option<u64> {
-    let rest: f64 = number as f64 / maybe_factor as f64;
-
-    if rest.round() == rest {
-        Some(rest as u64)
-    } else {
-        None
-    }
-}
-
-fn find_factors(number: u64) -> HashSet<u64> {
-    let mut maybe_factor = 2;
-    let mut rest = number;
-    let mut factors: HashSet<u64> = HashSet::new();
-
-    while rest >= maybe_factor {
-        if is_prime(maybe_factor) {
-            if let Some(new_rest) = is_factor(number, maybe_factor) {
-                rest = new_rest;
-                factors.insert(maybe_factor);
-            }
-        }
-        maybe_factor += 1;
-    }
-
-    factors
-}
-
+/// Ghosts step in lockstep, so they all first meet an end node after the least
+/// common multiple of their cycle lengths.
 fn find_ghost_sleep(directions: String, map: DesertMap) -> anyhow::Result<u128> {
-    let current_nodes: Vec<u64> = map
+    let cycles: Vec<u128> = map
         .keys()
         .filter(|key| key.ends_with('A'))
-        .map(|key| find_sleep(key.clone(), &directions, &map))
+        .map(|key| find_sleep(key.clone(), &directions, &map).map(u128::from))
         .collect::<Result<_, _>>()?;
 
-    let mut all_factors: HashSet<u128> = HashSet::new();
-
-    for node in current_nodes.iter() {
-        for factor in find_factors(*node) {
-            all_factors.insert(factor as u128);
-        }
-    }
-
-    Ok(all_factors.iter().product())
+    lcm_all(cycles)
+        .ok_or_else(|| Error::Overflow("Ghost steps do not fit in u128".to_string()).into())
 }
 
 pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<u128> {
@@ -142,3 +91,51 @@ pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<u128> {
         _ => Ok(0),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        // Given
+        let binding = vec![
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ];
+        let input = binding.iter().map(|line| line.to_string()).collect();
+
+        // When
+        let steps = challenge(1, input).unwrap();
+
+        // Then
+        assert_eq!(6, steps);
+    }
+
+    #[test]
+    fn part2_example() {
+        // Given
+        let binding = vec![
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "11B = (XXX, 11Z)",
+            "11Z = (11B, XXX)",
+            "22A = (22B, XXX)",
+            "22B = (22C, 22C)",
+            "22C = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+            "XXX = (XXX, XXX)",
+        ];
+        let input = binding.iter().map(|line| line.to_string()).collect();
+
+        // When
+        let steps = challenge(2, input).unwrap();
+
+        // Then
+        assert_eq!(6, steps);
+    }
+}
@@ -1,62 +1,384 @@
-fn parse_series(input: Vec<String>) -> anyhow::Result<Vec<Vec<i32>>> {
-    let mut series = Vec::new();
-    for line in input {
-        series.push(
-            line.split_whitespace()
-                .map(|it| it.parse::<i32>())
-                .collect::<Result<_, _>>()?,
-        );
-    }
-    Ok(series)
+use std::{fmt::Display, str::FromStr};
+
+use num_integer::Integer;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Signed};
+
+use crate::Error;
+
+/// Number type a serie can be extrapolated in. All arithmetic is checked so an
+/// overflow is reported instead of producing a wrong extrapolation.
+pub trait Value:
+    Integer
+    + Signed
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + CheckedDiv
+    + FromPrimitive
+    + Clone
+    + Display
+    + FromStr<Err: std::error::Error + Send + Sync + 'static>
+{
+}
+
+impl<T> Value for T where
+    T: Integer
+        + Signed
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + FromPrimitive
+        + Clone
+        + Display
+        + FromStr<Err: std::error::Error + Send + Sync + 'static>
+{
+}
+
+fn add<T: Value>(a: &T, b: &T) -> Result<T, Error> {
+    a.checked_add(b)
+        .ok_or_else(|| Error::Overflow(format!("{} + {} is out of range", a, b)))
+}
+
+fn sub<T: Value>(a: &T, b: &T) -> Result<T, Error> {
+    a.checked_sub(b)
+        .ok_or_else(|| Error::Overflow(format!("{} - {} is out of range", a, b)))
 }
 
-fn diffs(serie: Vec<i32>) -> Vec<Vec<i32>> {
-    let mut diffs: Vec<Vec<i32>> = Vec::new();
-    let mut current = serie.clone();
+fn mul<T: Value>(a: &T, b: &T) -> Result<T, Error> {
+    a.checked_mul(b)
+        .ok_or_else(|| Error::Overflow(format!("{} * {} is out of range", a, b)))
+}
+
+fn from_usize<T: Value>(value: usize) -> Result<T, Error> {
+    T::from_usize(value).ok_or_else(|| Error::Overflow(format!("{} is out of range", value)))
+}
 
-    while current.iter().any(|num| *num != 0) {
-        let mut next_diff = vec![];
+fn sum<T: Value>(mut values: impl Iterator<Item = anyhow::Result<T>>) -> anyhow::Result<T> {
+    values.try_fold(T::zero(), |sum, value| Ok(add(&sum, &value?)?))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SerieError {
+    InvalidNumber(String),
+    Empty,
+    TooShort { len: usize, degree: usize },
+    NotPolynomial { len: usize, max_degree: usize },
+}
 
-        for i in 1..current.len() {
-            next_diff.push(current[i] - current[i - 1]);
+impl std::fmt::Display for SerieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber(msg) => write!(f, "{}", msg),
+            Self::Empty => write!(f, "serie is empty"),
+            Self::TooShort { len, degree } => write!(
+                f,
+                "{} values are too few to confirm a polynomial of degree {}, {} needed",
+                len,
+                degree,
+                degree + 2
+            ),
+            Self::NotPolynomial { len, max_degree } => write!(
+                f,
+                "serie of {} values is not a polynomial of degree {} or lower",
+                len, max_degree
+            ),
         }
+    }
+}
+
+impl std::error::Error for SerieError {}
+
+fn parse_serie<T: Value>(line: &str) -> anyhow::Result<Vec<T>> {
+    line.split_whitespace()
+        .map(|it| {
+            it.parse::<T>()
+                .map_err(|err| SerieError::InvalidNumber(format!("{}: {}", it, err)).into())
+        })
+        .collect()
+}
+
+/// Difference rows of a serie, ending with an all-zero row. When the values
+/// run out first the last row is empty, and at most `max_rows` rows are built.
+fn diffs<T: Value>(serie: Vec<T>, max_rows: Option<usize>) -> anyhow::Result<Vec<Vec<T>>> {
+    let mut diffs: Vec<Vec<T>> = Vec::new();
+    let mut current = serie;
+
+    while current.iter().any(|num| !num.is_zero())
+        && max_rows.is_none_or(|max_rows| diffs.len() + 1 < max_rows)
+    {
+        let next_diff = current
+            .windows(2)
+            .map(|pair| sub(&pair[1], &pair[0]))
+            .collect::<Result<_, _>>()?;
+
         diffs.push(current);
         current = next_diff;
     }
 
     diffs.push(current);
-    diffs
+    Ok(diffs)
 }
 
-fn predict_next(serie: Vec<i32>) -> i32 {
-    diffs(serie).iter().map(|it| it.last().unwrap()).sum()
+/// Polynomial through a serie, stored in Newton forward-difference form:
+/// `p(n) = sum(newton[k] * binomial(n, k))` where `newton[k]` is the first
+/// element of the k:th difference row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial<T> {
+    newton: Vec<T>,
+    len: usize,
 }
 
-fn sum_next(series: Vec<Vec<i32>>) -> i32 {
-    series.into_iter().map(predict_next).sum()
+impl<T: Value> Polynomial<T> {
+    pub fn from_serie(serie: Vec<T>) -> anyhow::Result<Self> {
+        Self::fit(serie, None)
+    }
+
+    /// Fits a polynomial that is confirmed by the serie itself, i.e. whose
+    /// difference table reaches an all-zero row within the serie's length.
+    pub fn fit(serie: Vec<T>, max_degree: Option<usize>) -> anyhow::Result<Self> {
+        let len = serie.len();
+        if len == 0 {
+            return Err(SerieError::Empty.into());
+        }
+
+        let rows = diffs(serie, max_degree.map(|degree| degree + 2))?;
+        let last = rows.last().map_or(&[][..], |row| &row[..]);
+
+        // Without a maximum the differences run out only when no degree the
+        // serie is long enough to confirm, up to `len - 2`, fits it.
+        if last.is_empty() {
+            return Err(match max_degree {
+                None if len >= 2 => SerieError::NotPolynomial {
+                    len,
+                    max_degree: len - 2,
+                },
+                None => SerieError::TooShort { len, degree: 0 },
+                Some(degree) => SerieError::TooShort {
+                    len,
+                    degree: degree.min(len - 1),
+                },
+            }
+            .into());
+        }
+
+        if let Some(max_degree) = max_degree.filter(|_| last.iter().any(|it| !it.is_zero())) {
+            return Err(SerieError::NotPolynomial { len, max_degree }.into());
+        }
+
+        let mut newton: Vec<T> = rows
+            .into_iter()
+            .filter_map(|row| row.into_iter().next())
+            .collect();
+
+        while newton.last().is_some_and(|it| it.is_zero()) {
+            newton.pop();
+        }
+
+        Ok(Polynomial { newton, len })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.newton.len().saturating_sub(1)
+    }
+
+    pub fn newton_coefficients(&self) -> &[T] {
+        &self.newton
+    }
+
+    /// Length of the serie the polynomial was fitted to.
+    pub fn serie_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn next(&self) -> anyhow::Result<T> {
+        self.eval(&from_usize(self.len)?)
+    }
+
+    pub fn prev(&self) -> anyhow::Result<T> {
+        self.eval(&-T::one())
+    }
+
+    /// Value at index `n`, where index 0 is the first element of the serie.
+    pub fn eval(&self, n: &T) -> anyhow::Result<T> {
+        let mut binomial = T::one();
+        let mut sum = T::zero();
+
+        for (k, coefficient) in self.newton.iter().enumerate() {
+            if k > 0 {
+                // binomial(n, k) = binomial(n, k - 1) * (n - k + 1) / k, dividing
+                // out the common factor first to keep the intermediate small
+                let k = from_usize::<T>(k)?;
+                let factor = add(&sub(n, &k)?, &T::one())?;
+                let common = factor.gcd(&k);
+                binomial = mul(&(binomial / (k / common.clone())), &(factor / common))?;
+            }
+            sum = add(&sum, &mul(coefficient, &binomial)?)?;
+        }
+
+        Ok(sum)
+    }
+
+    /// Expanded coefficients as numerators over a common denominator,
+    /// lowest power first: `p(n) = sum(numerators[i] * n^i) / denominator`.
+    pub fn coefficients(&self) -> anyhow::Result<(Vec<T>, T)> {
+        let mut denominator = T::one();
+        for k in 1..=self.degree() {
+            denominator = mul(&denominator, &from_usize(k)?)?;
+        }
+        let mut numerators = vec![T::zero(); self.newton.len()];
+
+        // Coefficients of the falling factorial n * (n - 1) * ... * (n - k + 1)
+        let mut falling = vec![T::one()];
+        let mut factorial = T::one();
+
+        for (k, coefficient) in self.newton.iter().enumerate() {
+            if k > 0 {
+                let shift = from_usize::<T>(k - 1)?;
+                let mut next = vec![T::zero(); falling.len() + 1];
+                for (power, value) in falling.iter().enumerate() {
+                    next[power + 1] = add(&next[power + 1], value)?;
+                    next[power] = sub(&next[power], &mul(value, &shift)?)?;
+                }
+                falling = next;
+                factorial = mul(&factorial, &from_usize(k)?)?;
+            }
+
+            let scale = mul(coefficient, &(denominator.clone() / factorial.clone()))?;
+            for (power, value) in falling.iter().enumerate() {
+                numerators[power] = add(&numerators[power], &mul(&scale, value)?)?;
+            }
+        }
+
+        let divisor = numerators
+            .iter()
+            .fold(denominator.clone(), |divisor, numerator| {
+                divisor.gcd(numerator)
+            });
+
+        Ok((
+            numerators
+                .into_iter()
+                .map(|it| it / divisor.clone())
+                .collect(),
+            denominator / divisor,
+        ))
+    }
 }
 
-fn predict_prev(serie: Vec<i32>) -> i32 {
-    let diffs: Vec<Vec<i32>> = diffs(serie).into_iter().rev().collect();
+fn write_terms<T: Value>(
+    f: &mut std::fmt::Formatter<'_>,
+    terms: impl Iterator<Item = (T, String)>,
+) -> Result<bool, std::fmt::Error> {
+    let mut first = true;
+    for (coefficient, variable) in terms {
+        if coefficient.is_zero() {
+            continue;
+        }
 
-    let mut start = 0;
+        let sign = match (first, coefficient.is_negative()) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        let magnitude = coefficient.abs();
+        if magnitude.is_one() && !variable.is_empty() {
+            write!(f, "{}{}", sign, variable)?;
+        } else {
+            write!(f, "{}{}{}", sign, magnitude, variable)?;
+        }
+        first = false;
+    }
+    Ok(!first)
+}
 
-    for i in 1..diffs.len() {
-        start = diffs[i].first().unwrap() - start;
+/// Writes the expanded formula, falling back to the Newton form when the
+/// expanded coefficients do not fit in the value type.
+impl<T: Value> std::fmt::Display for Polynomial<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let written = match self.coefficients() {
+            Ok((numerators, denominator)) => {
+                let terms = numerators.into_iter().enumerate().rev().map(|(power, it)| {
+                    let variable = match power {
+                        0 => String::new(),
+                        1 => "n".to_string(),
+                        power => format!("n^{}", power),
+                    };
+                    (it, variable)
+                });
+
+                if denominator.is_one() {
+                    write_terms(f, terms)?
+                } else {
+                    write!(f, "(")?;
+                    write_terms(f, terms)?;
+                    write!(f, ") / {}", denominator)?;
+                    true
+                }
+            }
+            Err(_) => write_terms(
+                f,
+                self.newton.iter().enumerate().map(|(k, it)| {
+                    let variable = match k {
+                        0 => String::new(),
+                        k => format!("C(n,{})", k),
+                    };
+                    (it.clone(), variable)
+                }),
+            )?,
+        };
+
+        if !written {
+            write!(f, "0")?;
+        }
+        Ok(())
     }
+}
+
+pub fn sum_next<T: Value>(polynomials: &[Polynomial<T>]) -> anyhow::Result<T> {
+    sum(polynomials.iter().map(Polynomial::next))
+}
 
-    start
+pub fn sum_prev<T: Value>(polynomials: &[Polynomial<T>]) -> anyhow::Result<T> {
+    sum(polynomials.iter().map(Polynomial::prev))
 }
 
-fn sum_prev(series: Vec<Vec<i32>>) -> i32 {
-    series.into_iter().map(predict_prev).sum()
+pub fn sum_at<T: Value>(index: &T, polynomials: &[Polynomial<T>]) -> anyhow::Result<T> {
+    sum(polynomials.iter().map(|polynomial| polynomial.eval(index)))
 }
 
-pub fn challenge(part: u32, input: Vec<String>) -> anyhow::Result<i32> {
+/// Fits a polynomial to every line, reporting all lines that are not valid
+/// series together.
+pub fn polynomials<T: Value>(
+    input: Vec<String>,
+    max_degree: Option<usize>,
+) -> anyhow::Result<Vec<Polynomial<T>>> {
+    let mut polynomials = Vec::new();
+    let mut problems: Vec<String> = Vec::new();
+
+    for (index, line) in input.iter().enumerate() {
+        match parse_serie(line).and_then(|serie| Polynomial::fit(serie, max_degree)) {
+            Ok(polynomial) => polynomials.push(polynomial),
+            Err(err) => match err.downcast::<SerieError>() {
+                Ok(serie_err) => problems.push(format!("line {}: {}", index + 1, serie_err)),
+                Err(err) => return Err(err),
+            },
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(Error::InvalidInput(problems.join("\n")).into());
+    }
+
+    Ok(polynomials)
+}
+
+pub fn challenge<T: Value>(part: u32, input: Vec<String>) -> anyhow::Result<T> {
     match part {
-        1 => Ok(sum_next(parse_series(input)?)),
-        2 => Ok(sum_prev(parse_series(input)?)),
-        _ => Ok(0),
+        1 => sum_next(&polynomials(input, None)?),
+        2 => sum_prev(&polynomials(input, None)?),
+        _ => Ok(T::zero()),
     }
 }
 
@@ -74,9 +396,246 @@ mod tests {
         ];
 
         // When
-        let sum = challenge(2, input).unwrap();
+        let sum: i64 = challenge(2, input).unwrap();
 
         // Then
         assert_eq!(2, sum);
     }
+
+    #[test]
+    fn part1_example() {
+        // Given
+        let input = vec![
+            "0 3 6 9 12 15".to_string(),
+            "1 3 6 10 15 21".to_string(),
+            "10 13 16 21 30 45".to_string(),
+        ];
+
+        // When
+        let sum: i64 = challenge(1, input).unwrap();
+
+        // Then
+        assert_eq!(114, sum);
+    }
+
+    #[test]
+    fn polynomial_from_serie() {
+        // Given
+        let serie: Vec<i64> = vec![10, 13, 16, 21, 30, 45];
+
+        // When
+        let polynomial = Polynomial::from_serie(serie).unwrap();
+
+        // Then
+        assert_eq!(3, polynomial.degree());
+        assert_eq!(&[10, 3, 0, 2], polynomial.newton_coefficients());
+        assert_eq!((vec![30, 11, -3, 1], 3), polynomial.coefficients().unwrap());
+        assert_eq!("(n^3 - 3n^2 + 11n + 30) / 3", polynomial.to_string());
+    }
+
+    #[test]
+    fn polynomial_eval_far_and_negative() {
+        // Given
+        let polynomial = Polynomial::from_serie(vec![1_i64, 3, 6, 10, 15, 21]).unwrap();
+
+        // When - Then
+        assert_eq!(28, polynomial.eval(&6).unwrap());
+        assert_eq!(0, polynomial.eval(&-1).unwrap());
+        assert_eq!(0, polynomial.eval(&-2).unwrap());
+        assert_eq!(1, polynomial.eval(&-3).unwrap());
+        assert_eq!(5151, polynomial.eval(&100).unwrap());
+        assert_eq!(4950, polynomial.eval(&-101).unwrap());
+        assert_eq!(500_001_500_001, polynomial.eval(&1_000_000).unwrap());
+    }
+
+    #[test]
+    fn polynomial_display() {
+        // Given
+        let linear = Polynomial::from_serie(vec![0_i64, 3, 6, 9]).unwrap();
+        let falling = Polynomial::from_serie(vec![5_i64, 4, 3]).unwrap();
+        let constant = Polynomial::from_serie(vec![-7_i64, -7, -7]).unwrap();
+        let zero = Polynomial::from_serie(vec![0_i64, 0]).unwrap();
+
+        // When - Then
+        assert_eq!("3n", linear.to_string());
+        assert_eq!("-n + 5", falling.to_string());
+        assert_eq!("-7", constant.to_string());
+        assert_eq!("0", zero.to_string());
+    }
+
+    #[test]
+    fn beyond_i32() {
+        // Given
+        let input = vec![
+            "2000000000 2100000000 2200000000".to_string(),
+            "-2147483648 -2147483647 -2147483646".to_string(),
+        ];
+
+        // When
+        let next: i64 = challenge(1, input.clone()).unwrap();
+        let prev: i64 = challenge(2, input).unwrap();
+
+        // Then
+        assert_eq!(2_300_000_000 - 2_147_483_645, next);
+        assert_eq!(1_900_000_000 - 2_147_483_649, prev);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        // Given
+        let extrapolation = vec![format!("{} {} {}", i64::MAX - 2, i64::MAX - 1, i64::MAX)];
+        let difference = vec![format!("{} {} {}", i64::MIN, i64::MAX, i64::MAX)];
+        let sum = vec![
+            format!("{} {} {}", i64::MAX - 4, i64::MAX - 3, i64::MAX - 2),
+            format!("{} {} {}", i64::MAX - 4, i64::MAX - 3, i64::MAX - 2),
+        ];
+
+        // When
+        let extrapolation_result = challenge::<i64>(1, extrapolation);
+        let difference_result = challenge::<i64>(1, difference);
+        let sum_result = challenge::<i64>(1, sum);
+
+        // Then
+        for result in [extrapolation_result, difference_result, sum_result] {
+            assert!(matches!(
+                result.unwrap_err().downcast_ref::<Error>(),
+                Some(Error::Overflow(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn formula_falls_back_to_newton_form() {
+        // Given
+        let serie: Vec<i64> = (0..23)
+            .map(|n| match n {
+                21 => 1,
+                22 => 22,
+                _ => 0,
+            })
+            .collect();
+
+        // When
+        let polynomial = Polynomial::from_serie(serie).unwrap();
+
+        // Then
+        assert!(polynomial.coefficients().is_err());
+        assert_eq!("C(n,21)", polynomial.to_string());
+    }
+
+    #[test]
+    fn fit_rejects_invalid_series() {
+        // Given
+        let empty: Vec<i64> = vec![];
+        let single = vec![5_i64];
+        let unconfirmed = vec![1_i64, 4, 9];
+        let exponential = vec![1_i64, 2, 4, 8, 16, 32];
+
+        // When - Then
+        for (serie, max_degree, expected) in [
+            (empty, None, SerieError::Empty),
+            (single, None, SerieError::TooShort { len: 1, degree: 0 }),
+            (
+                unconfirmed.clone(),
+                None,
+                SerieError::NotPolynomial {
+                    len: 3,
+                    max_degree: 1,
+                },
+            ),
+            (
+                unconfirmed[..2].to_vec(),
+                Some(1),
+                SerieError::TooShort { len: 2, degree: 1 },
+            ),
+            (
+                unconfirmed,
+                Some(1),
+                SerieError::NotPolynomial {
+                    len: 3,
+                    max_degree: 1,
+                },
+            ),
+            (
+                exponential.clone(),
+                Some(3),
+                SerieError::NotPolynomial {
+                    len: 6,
+                    max_degree: 3,
+                },
+            ),
+            (
+                exponential,
+                None,
+                SerieError::NotPolynomial {
+                    len: 6,
+                    max_degree: 4,
+                },
+            ),
+        ] {
+            let err = Polynomial::fit(serie, max_degree).unwrap_err();
+            assert_eq!(Some(&expected), err.downcast_ref::<SerieError>());
+        }
+    }
+
+    #[test]
+    fn fit_with_max_degree() {
+        // Given
+        let serie = vec![1_i64, 4, 9, 16, 25];
+
+        // When
+        let polynomial = Polynomial::fit(serie, Some(2)).unwrap();
+
+        // Then
+        assert_eq!(2, polynomial.degree());
+        assert_eq!(5, polynomial.serie_len());
+        assert_eq!(36, polynomial.next().unwrap());
+        assert_eq!(0, polynomial.prev().unwrap());
+    }
+
+    #[test]
+    fn errors_are_reported_per_line() {
+        // Given
+        let input = vec![
+            "0 3 6 9 12 15".to_string(),
+            "".to_string(),
+            "1 2 4 8".to_string(),
+            "1 x 3".to_string(),
+        ];
+
+        // When
+        let err = challenge::<i64>(1, input).unwrap_err();
+
+        // Then
+        assert_eq!(
+            Some(
+                "line 2: serie is empty\n\
+                  line 3: serie of 4 values is not a polynomial of degree 2 or lower\n\
+                  line 4: x: invalid digit found in string"
+            ),
+            match err.downcast_ref::<Error>() {
+                Some(Error::InvalidInput(msg)) => Some(msg.as_str()),
+                _ => None,
+            }
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_integers() {
+        // Given
+        let input = vec![format!("{} {} {}", i64::MAX - 2, i64::MAX - 1, i64::MAX)];
+
+        // When
+        let next: num_bigint::BigInt = challenge(1, input.clone()).unwrap();
+        let far: num_bigint::BigInt = sum_at(
+            &"100000000000000000000".parse().unwrap(),
+            &polynomials(input, None).unwrap(),
+        )
+        .unwrap();
+
+        // Then
+        assert_eq!("9223372036854775808", next.to_string());
+        assert_eq!("109223372036854775805", far.to_string());
+    }
 }
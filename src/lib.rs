@@ -14,6 +14,7 @@ pub mod parse;
 pub enum Error {
     MissingArg,
     InvalidInput(String),
+    Overflow(String),
 }
 
 impl std::fmt::Display for Error {
@@ -21,6 +22,7 @@ impl std::fmt::Display for Error {
         match self {
             Self::MissingArg => write!(f, "Missing argument"),
             Self::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            Self::Overflow(msg) => write!(f, "Overflow: {}", msg),
         }
     }
 }
@@ -1,26 +1,217 @@
-use adc23::Error;
+use adc23::{
+    parse::{find_option, has_flag},
+    Error,
+};
 
 fn read_std_in() -> anyhow::Result<Vec<String>> {
     Ok(std::io::stdin().lines().collect::<Result<_, _>>()?)
 }
 
+fn day1(part: u32, options: &[String]) -> anyhow::Result<u64> {
+    let mut vocabulary = match (part, find_option(options, "--vocabulary")) {
+        (1, _) => adc23::day1::Vocabulary::plain_digits(),
+        (2, Some(vocabulary)) => vocabulary.parse()?,
+        (2, None) => adc23::day1::Vocabulary::english(),
+        _ => return Ok(0),
+    };
+    vocabulary.ignore_case = has_flag(options, "--ignore-case");
+    vocabulary.unicode_digits = has_flag(options, "--unicode-digits");
+
+    let calibration = adc23::day1::calibrate(&read_std_in()?, &vocabulary);
+
+    if has_flag(options, "--explain") {
+        for value in calibration.values.iter() {
+            println!("{}", value);
+        }
+        for warning in calibration.warnings() {
+            println!("warning: {}", warning);
+        }
+    }
+
+    Ok(calibration.sum())
+}
+
+fn day2(part: u32, options: &[String]) -> anyhow::Result<u32> {
+    let config = adc23::day2::Config {
+        bag: match find_option(options, "--bag") {
+            Some(bag) => bag.parse()?,
+            None => adc23::day2::default_bag(),
+        },
+        strict: has_flag(options, "--strict"),
+    };
+    let input = read_std_in()?;
+
+    match find_option(options, "--report") {
+        Some("table") => println!(
+            "{}",
+            adc23::day2::report_table(&adc23::day2::reports(input.clone(), &config)?)?
+        ),
+        Some("json") => println!(
+            "{}",
+            adc23::day2::report_json(&adc23::day2::reports(input.clone(), &config)?)?
+        ),
+        Some(format) => {
+            return Err(Error::InvalidInput(format!("Unknown report format {}", format)).into())
+        }
+        None => {}
+    }
+
+    adc23::day2::elf_challenge_with_config(part, input, &config)
+}
+
+fn day3(part: u32, options: &[String]) -> anyhow::Result<u32> {
+    let mut config = adc23::day3::Config::default();
+    if let Some(blank) = find_option(options, "--blank") {
+        let mut chars = blank.chars();
+        config.blank = match (chars.next(), chars.next()) {
+            (Some(blank), None) => blank,
+            _ => {
+                return Err(
+                    Error::InvalidInput(format!("{} is not a single character", blank)).into(),
+                )
+            }
+        };
+    }
+    if let Some(symbols) = find_option(options, "--gear-symbols") {
+        config.gear_rules.symbols = symbols.chars().collect();
+    }
+    if let Some(adjacent) = find_option(options, "--adjacent") {
+        config.gear_rules.adjacent = adjacent.parse()?;
+    }
+    if let Some(combine) = find_option(options, "--combine") {
+        config.gear_rules.combine = combine.parse()?;
+    }
+    let input = read_std_in()?;
+
+    if has_flag(options, "--render") {
+        print!("{}", adc23::day3::render(input.clone(), &config)?);
+        println!("Non-part numbers:");
+        for (pos, value) in adc23::day3::non_part_numbers(input.clone(), &config)? {
+            println!("{} {}", pos, value);
+        }
+    }
+
+    if has_flag(options, "--gears") {
+        for gear in adc23::day3::gears(input.clone(), &config)? {
+            println!("{}", gear);
+        }
+    }
+
+    adc23::day3::challenge_with_config(part, input, &config)
+}
+
+fn day4(part: u32, options: &[String]) -> anyhow::Result<u64> {
+    use adc23::day4::{CopyRules, Rule};
+
+    let mut copy_rules = CopyRules::default();
+    if let Some(window) = find_option(options, "--window") {
+        copy_rules.window = Some(window.parse()?);
+    }
+    if let Some(past_last) = find_option(options, "--past-last") {
+        copy_rules.past_last = past_last.parse()?;
+    }
+    let rule = match (find_option(options, "--rule"), part) {
+        (Some(rule), _) => rule.parse()?,
+        (None, 1) => Rule::Doubling,
+        (None, 2) => Rule::CopiesWon(copy_rules),
+        _ => return Ok(0),
+    };
+    let rule = match rule {
+        Rule::CopiesWon(_) => Rule::CopiesWon(copy_rules),
+        other => other,
+    };
+    let input = read_std_in()?;
+
+    if has_flag(options, "--trace") {
+        let cards = adc23::day4::parse_cards(input.clone())?;
+        for card in adc23::day4::copies(&cards, &copy_rules)? {
+            println!("{}", card);
+        }
+    }
+
+    adc23::day4::challenge_with_rule(input, &rule)
+}
+
+fn day7(part: u32, options: &[String]) -> anyhow::Result<u32> {
+    use adc23::day7::{Card, Rules};
+
+    let mut rules = match (find_option(options, "--rules"), part) {
+        (Some(rules), _) => rules.parse()?,
+        (None, 1) => Rules::standard(),
+        (None, 2) => Rules::jokers(),
+        _ => return Ok(0),
+    };
+    if let Some(wilds) = find_option(options, "--wild") {
+        rules.wilds = wilds
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(wild_rank) = find_option(options, "--wild-rank") {
+        rules.wild_rank = wild_rank.parse()?;
+    }
+    if let Some(order) = find_option(options, "--order") {
+        rules.order = Rules::parse_order(order)?;
+    }
+    if let Some(hand_size) = find_option(options, "--hand-size") {
+        rules.hand_size = hand_size.parse()?;
+    }
+
+    if has_flag(options, "--explain") {
+        let input = read_std_in()?;
+        for explanation in adc23::day7::explain(&mut input.iter().cloned().map(Ok), &rules)? {
+            println!("{}", explanation);
+        }
+        return adc23::day7::challenge_with_rules(&mut input.into_iter().map(Ok), &rules);
+    }
+
+    adc23::day7::challenge_with_rules(&mut std::io::stdin().lines(), &rules)
+}
+
+fn day9<T: adc23::day9::Value>(part: u32, options: &[String]) -> anyhow::Result<T> {
+    let max_degree = find_option(options, "--max-degree")
+        .map(|degree| degree.parse())
+        .transpose()?;
+    let polynomials = adc23::day9::polynomials::<T>(read_std_in()?, max_degree)?;
+
+    if has_flag(options, "--formula") {
+        for polynomial in polynomials.iter() {
+            println!("{}", polynomial);
+        }
+    }
+
+    match (find_option(options, "--at"), part) {
+        (Some(index), _) => adc23::day9::sum_at(&index.parse()?, &polynomials),
+        (None, 1) => adc23::day9::sum_next(&polynomials),
+        (None, 2) => adc23::day9::sum_prev(&polynomials),
+        (None, _) => Ok(T::zero()),
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     let day: u32 = std::env::args().nth(1).ok_or(Error::MissingArg)?.parse()?;
     let part: u32 = std::env::args().nth(2).ok_or(Error::MissingArg)?.parse()?;
+    let options: Vec<String> = std::env::args().skip(3).collect();
 
     let result = match day {
-        1 => Ok(adc23::day1::sum_calibration_values(part, read_std_in()?) as u128),
-        2 => adc23::day2::elf_challenge(part, read_std_in()?).map(|it| it as u128),
-        3 => adc23::day3::challenge(part, read_std_in()?).map(|it| it as u128),
-        4 => adc23::day4::challenge(part, read_std_in()?).map(|it| it as u128),
-        5 => adc23::day5::challenge(part, &mut std::io::stdin().lines()).map(|it| it as u128),
-        6 => adc23::day6::challenge(part, &mut std::io::stdin().lines()).map(|it| it as u128),
-        7 => adc23::day7::challenge(part, &mut std::io::stdin().lines()).map(|it| it as u128),
-        8 => adc23::day8::challenge(part, read_std_in()?),
-        9 => adc23::day9::challenge(part, read_std_in()?).map(|it| {
-            println!("Real answer {}", it);
-            it as u128
-        }),
+        1 => day1(part, &options).map(|it| it.to_string()),
+        2 => day2(part, &options).map(|it| it.to_string()),
+        3 => day3(part, &options).map(|it| it.to_string()),
+        4 => day4(part, &options).map(|it| it.to_string()),
+        5 => adc23::day5::challenge(part, &mut std::io::stdin().lines()).map(|it| it.to_string()),
+        6 => adc23::day6::challenge(part, &mut std::io::stdin().lines()).map(|it| it.to_string()),
+        7 => day7(part, &options).map(|it| it.to_string()),
+        8 => adc23::day8::challenge(part, read_std_in()?).map(|it| it.to_string()),
+        #[cfg(feature = "bigint")]
+        9 if has_flag(&options, "--big") => {
+            day9::<num_bigint::BigInt>(part, &options).map(|it| it.to_string())
+        }
+        #[cfg(not(feature = "bigint"))]
+        9 if has_flag(&options, "--big") => Err(Error::InvalidInput(
+            "--big requires building with --features bigint".to_string(),
+        )
+        .into()),
+        9 => day9::<i64>(part, &options).map(|it| it.to_string()),
         day => Err(Error::InvalidInput(format!("Day {} is not implemented", day)).into()),
     }?;
 
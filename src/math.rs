@@ -1,27 +1,77 @@
+pub mod number;
+
+use num_traits::Signed;
+
+use crate::Error;
+
+/// What a `Point` can be made of: any signed integer type.
+pub trait Coordinate: Signed + Ord + Copy {}
+
+impl<T: Signed + Ord + Copy> Coordinate for T {}
+
+/// Position or offset on a grid, `i32` unless large coordinates call for
+/// `Point<i64>`.
 #[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
 
-    pub fn adjacent(&self, other: &Point) -> bool {
-        self.x.abs_diff(other.x) <= 1 && self.y.abs_diff(other.y) <= 1
+    /// Whether the points are at most one king move apart. Unlike `chebyshev`
+    /// this never overflows, however far apart the points are.
+    pub fn adjacent(&self, other: &Point<T>) -> bool {
+        // Stepping the larger coordinate down by one cannot go below the
+        // smaller one, so it never underflows.
+        fn near<T: Coordinate>(a: T, b: T) -> bool {
+            match a.cmp(&b) {
+                std::cmp::Ordering::Equal => true,
+                std::cmp::Ordering::Greater => a - T::one() <= b,
+                std::cmp::Ordering::Less => b - T::one() <= a,
+            }
+        }
+
+        near(self.x, other.x) && near(self.y, other.y)
+    }
+
+    pub fn manhattan(&self, other: &Point<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The number of king moves between the points.
+    pub fn chebyshev(&self, other: &Point<T>) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The points above, right of, below and left of this one.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point<T>> {
+        Direction::CARDINAL
+            .into_iter()
+            .map(move |dir| self.step(dir))
+    }
+
+    /// The points around this one, clockwise from above.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point<T>> {
+        Direction::ALL.into_iter().map(move |dir| self.step(dir))
     }
 }
 
-impl std::fmt::Display for Point {
+impl<T: std::fmt::Display> std::fmt::Display for Point<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({},{})", self.x, self.y)
     }
 }
 
-impl std::ops::Add for Point {
-    type Output = Point;
+impl<T: Coordinate> std::ops::Add for Point<T> {
+    type Output = Point<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         Point {
@@ -30,3 +80,504 @@ impl std::ops::Add for Point {
         }
     }
 }
+
+impl<T: Coordinate> std::ops::Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Coordinate> std::ops::Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Coordinate> std::ops::Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Compass directions on a grid where `y` grows downwards, so `North` is
+/// `(0,-1)`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Clockwise from `North`.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn offset<T: Coordinate>(self) -> Point<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let (x, y) = match self {
+            Direction::North => (zero, -one),
+            Direction::NorthEast => (one, -one),
+            Direction::East => (one, zero),
+            Direction::SouthEast => (one, one),
+            Direction::South => (zero, one),
+            Direction::SouthWest => (-one, one),
+            Direction::West => (-one, zero),
+            Direction::NorthWest => (-one, -one),
+        };
+        Point::new(x, y)
+    }
+
+    /// Turns clockwise by `eighths` of a full turn, counterclockwise when
+    /// negative.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+/// Dense rectangular grid stored row by row, with `(0,0)` at the top left and
+/// `y` growing downwards.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Fails when the rows are not all as wide as the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::InvalidInput(format!(
+                    "Line {} is {} wide, expected {}",
+                    y + 1,
+                    row.len(),
+                    width
+                )));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one cell per char of each line.
+    pub fn parse<F: FnMut(char) -> Result<T, Error>>(
+        lines: &[String],
+        mut parse_cell: F,
+    ) -> Result<Self, Error> {
+        let rows = lines
+            .iter()
+            .map(|line| line.chars().map(&mut parse_cell).collect())
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.index(pos).is_some()
+    }
+
+    fn index(&self, pos: Point) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.index(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.index(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.point(index), cell))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of {} columns", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells above, right of, below and left of `pos` inside the grid.
+    pub fn neighbors4(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.neighbors4()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// The cells around `pos` inside the grid, clockwise from above.
+    pub fn neighbors8(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.neighbors8()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    pub fn map<U, F: FnMut(Point, &T) -> U>(&self, mut f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+
+    /// Builds a `width` by `height` grid taking each cell from this grid.
+    fn rearranged<F: Fn(usize, usize) -> usize>(
+        &self,
+        width: usize,
+        height: usize,
+        source: F,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.cells[source(x, y)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| x * self.width + y)
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| {
+            (self.height - 1 - x) * self.width + y
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| {
+            x * self.width + self.width - 1 - y
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |x, y| {
+            y * self.width + self.width - 1 - x
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |x, y| {
+            (self.height - 1 - y) * self.width + x
+        })
+    }
+}
+
+impl<T> std::ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, width, height))
+    }
+}
+
+impl<T> std::ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, width, height))
+    }
+}
+
+/// One line per row, cells written next to each other.
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(lines: &[&str]) -> Grid<char> {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        Grid::parse(&lines, Ok).unwrap()
+    }
+
+    #[test]
+    fn verify_point_arithmetic() {
+        // Given
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        // Then
+        assert_eq!(Point::new(4, -6), a - b);
+        assert_eq!(Point::new(-3, 2), -a);
+        assert_eq!(Point::new(9, -6), a * 3);
+        assert_eq!(10, a.manhattan(&b));
+        assert_eq!(6, a.chebyshev(&b));
+        assert!(a.adjacent(&Point::new(4, -1)));
+        assert!(!a.adjacent(&Point::new(5, -2)));
+        assert!(!Point::new(i32::MIN, 0).adjacent(&Point::new(i32::MAX, 0)));
+        assert!(!Point::new(0, i32::MAX).adjacent(&Point::new(0, i32::MIN)));
+        assert!(Point::new(i32::MAX, i32::MIN).adjacent(&Point::new(i32::MAX - 1, i32::MIN + 1)));
+    }
+
+    #[test]
+    fn verify_wide_points() {
+        // Given
+        let far: Point<i64> = Point::new(5_000_000_000, -3_000_000_000);
+
+        // When
+        let moved = far.step(Direction::SouthWest) * 2;
+
+        // Then
+        assert_eq!(Point::new(9_999_999_998, -5_999_999_998), moved);
+        assert_eq!(8_000_000_000, far.manhattan(&Point::new(0, 0)));
+        assert_eq!("(5000000000,-3000000000)", far.to_string());
+    }
+
+    #[test]
+    fn verify_directions() {
+        // Then
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.opposite());
+        assert_eq!(Direction::NorthWest, Direction::North.rotate(-1));
+        assert_eq!(Direction::North, Direction::NorthWest.rotate(9));
+        assert!(Direction::SouthEast.is_diagonal());
+        assert!(!Direction::South.is_diagonal());
+        for dir in Direction::ALL {
+            assert_eq!(-dir.offset::<i32>(), dir.opposite().offset());
+            assert_eq!(dir, dir.turn_right().turn_right().opposite());
+        }
+    }
+
+    #[test]
+    fn verify_point_neighbors() {
+        // Given
+        let origin: Point = Point::new(0, 0);
+
+        // When
+        let four: Vec<Point> = origin.neighbors4().collect();
+        let eight: Vec<Point> = origin.neighbors8().collect();
+
+        // Then
+        assert_eq!(
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ],
+            four
+        );
+        assert_eq!(8, eight.len());
+        assert!(eight.iter().all(|pos| origin.chebyshev(pos) == 1));
+    }
+
+    #[test]
+    fn verify_grid_access() {
+        // Given
+        let mut grid = grid(&["abc", "def"]);
+
+        // When
+        grid[Point::new(2, 1)] = 'F';
+
+        // Then
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&'b'), grid.get(Point::new(1, 0)));
+        assert_eq!('F', grid[Point::new(2, 1)]);
+        assert_eq!(None, grid.get(Point::new(3, 0)));
+        assert_eq!(None, grid.get(Point::new(0, -1)));
+        assert!(!grid.contains(Point::new(0, 2)));
+        assert_eq!(&['d', 'e', 'F'], grid.row(1));
+        assert_eq!(vec!['c', 'F'], grid.column(2).copied().collect::<Vec<_>>());
+        assert_eq!(3, grid.columns().count());
+        assert_eq!((Point::new(1, 1), &'e'), grid.iter().nth(4).unwrap());
+    }
+
+    #[test]
+    fn verify_grid_neighbors() {
+        // Given
+        let grid = grid(&["abc", "def", "ghi"]);
+
+        // When
+        let corner4: Vec<char> = grid.neighbors4(Point::new(0, 0)).map(|(_, c)| *c).collect();
+        let center4: Vec<char> = grid.neighbors4(Point::new(1, 1)).map(|(_, c)| *c).collect();
+        let corner8: Vec<char> = grid.neighbors8(Point::new(2, 2)).map(|(_, c)| *c).collect();
+        let center8 = grid.neighbors8(Point::new(1, 1)).count();
+
+        // Then
+        assert_eq!(vec!['b', 'd'], corner4);
+        assert_eq!(vec!['b', 'f', 'h', 'd'], center4);
+        assert_eq!(vec!['f', 'h', 'e'], corner8);
+        assert_eq!(8, center8);
+    }
+
+    #[test]
+    fn verify_grid_transforms() {
+        // Given
+        let grid = grid(&["abc", "def"]);
+
+        // Then
+        assert_eq!("ad\nbe\ncf\n", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc\n", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad\n", grid.rotate_counterclockwise().to_string());
+        assert_eq!("cba\nfed\n", grid.flip_horizontal().to_string());
+        assert_eq!("def\nabc\n", grid.flip_vertical().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counterclockwise());
+        assert_eq!(
+            "0a1b2c\n0d1e2f\n",
+            grid.map(|pos, c| format!("{}{}", pos.x, c)).to_string()
+        );
+    }
+
+    #[test]
+    fn verify_grid_errors() {
+        // Given
+        let lines = vec!["abc".to_string(), "de".to_string()];
+
+        // When
+        let ragged = Grid::parse(&lines, Ok);
+        let invalid = Grid::parse(&lines, |c| {
+            c.to_digit(10)
+                .ok_or_else(|| Error::InvalidInput(format!("{} is not a digit", c)))
+        });
+
+        // Then
+        assert_eq!(
+            "Invalid input: Line 2 is 2 wide, expected 3",
+            ragged.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Invalid input: a is not a digit",
+            invalid.unwrap_err().to_string()
+        );
+    }
+}
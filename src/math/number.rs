@@ -0,0 +1,326 @@
+use num_integer::Integer;
+use num_traits::{CheckedMul, CheckedSub, Signed};
+
+use crate::Error;
+
+/// Greatest common divisor of all values, 0 when there are none.
+pub fn gcd_all<T: Integer + Clone, I: IntoIterator<Item = T>>(values: I) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |gcd, value| gcd.gcd(&value))
+}
+
+/// Least common multiple, never negative, or `None` when it does not fit.
+pub fn checked_lcm<T: Integer + CheckedMul + CheckedSub + Clone>(a: &T, b: &T) -> Option<T> {
+    if a.is_zero() || b.is_zero() {
+        return Some(T::zero());
+    }
+
+    let lcm = (a.clone() / a.gcd(b)).checked_mul(b)?;
+    if lcm < T::zero() {
+        T::zero().checked_sub(&lcm)
+    } else {
+        Some(lcm)
+    }
+}
+
+/// Least common multiple of all values, 1 when there are none, or `None` when
+/// it does not fit.
+pub fn lcm_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer + CheckedMul + CheckedSub + Clone,
+    I: IntoIterator<Item = T>,
+{
+    values
+        .into_iter()
+        .try_fold(T::one(), |lcm, value| checked_lcm(&lcm, &value))
+}
+
+/// `(gcd, x, y)` such that `a * x + b * y = gcd`.
+pub fn extended_gcd<T: Integer + Signed + Copy>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r.div_floor(&r);
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, if `a` and
+/// `modulus` are coprime. `None` when `modulus` is not positive.
+pub fn mod_inverse<T: Integer + Signed + Copy>(a: T, modulus: T) -> Option<T> {
+    if !modulus.is_positive() {
+        return None;
+    }
+
+    let (gcd, x, _) = extended_gcd(a.mod_floor(&modulus), modulus);
+
+    if gcd.is_one() {
+        Some(x.mod_floor(&modulus))
+    } else {
+        None
+    }
+}
+
+/// Solves `x = residue (mod modulus)` for every `(residue, modulus)` pair,
+/// moduli need not be coprime. Gives the smallest non-negative `x` with the
+/// least common multiple of the moduli, or `None` when the congruences
+/// contradict each other.
+pub fn crt<I: IntoIterator<Item = (i64, i64)>>(
+    congruences: I,
+) -> Result<Option<(i64, i64)>, Error> {
+    let mut solution: i128 = 0;
+    let mut combined: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return Err(Error::InvalidInput(format!(
+                "Modulus {} is not positive",
+                modulus
+            )));
+        }
+
+        let modulus = i128::from(modulus);
+        let (gcd, x, _) = extended_gcd(combined, modulus);
+        let difference = i128::from(residue) - solution;
+        if difference % gcd != 0 {
+            return Ok(None);
+        }
+
+        // combined * x = gcd (mod modulus), so stepping by combined * k with
+        // k = difference / gcd * x lands on the new residue.
+        let step = modulus / gcd;
+        let k = ((difference / gcd) % step * (x % step)).mod_floor(&step);
+        solution += combined * k;
+        combined *= step;
+
+        if combined > i128::from(i64::MAX) {
+            return Err(Error::Overflow(format!(
+                "Combined modulus {} does not fit in i64",
+                combined
+            )));
+        }
+        solution = solution.mod_floor(&combined);
+    }
+
+    Ok(Some((solution as i64, combined as i64)))
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from a power of two above the root converges down onto
+    // it without overflowing.
+    let mut root = 1 << (64 - n.leading_zeros()).div_ceil(2);
+    let mut next = (root + n / root) / 2;
+    while next < root {
+        root = next;
+        next = (root + n / root) / 2;
+    }
+
+    root
+}
+
+pub fn is_prime(n: u64) -> bool {
+    if n == 2 || n == 3 {
+        return true;
+    }
+
+    if n <= 1 || n.is_multiple_of(2) || n.is_multiple_of(3) {
+        return false;
+    }
+
+    (5..=isqrt(n))
+        .step_by(6)
+        .all(|i| !n.is_multiple_of(i) && !n.is_multiple_of(i + 2))
+}
+
+/// Every prime up to and including `limit`, by the sieve of Eratosthenes.
+pub fn primes_up_to(limit: usize) -> Vec<u64> {
+    let mut is_composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+
+    for n in 2..=limit {
+        if is_composite[n] {
+            continue;
+        }
+        primes.push(n as u64);
+        for multiple in (n * n..=limit).step_by(n) {
+            is_composite[multiple] = true;
+        }
+    }
+
+    primes
+}
+
+/// Prime factors of `n` with their multiplicity, smallest first. Empty for 0
+/// and 1.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+
+    let mut rest = n;
+    let candidates = [2, 3]
+        .into_iter()
+        .chain((5..).step_by(6).flat_map(|i| [i, i + 2]));
+    for candidate in candidates {
+        if candidate > rest / candidate {
+            break;
+        }
+
+        let mut power = 0;
+        while rest.is_multiple_of(candidate) {
+            rest /= candidate;
+            power += 1;
+        }
+        if power > 0 {
+            factors.push((candidate, power));
+        }
+    }
+
+    if rest > 1 {
+        factors.push((rest, 1));
+    }
+
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn verify_gcd_lcm() {
+        // Then
+        assert_eq!(6, gcd_all([12_u64, 18, 30]));
+        assert_eq!(0, gcd_all(Vec::<u64>::new()));
+        assert_eq!(3, gcd_all([-9_i64, 6]));
+        assert_eq!(Some(180), lcm_all([12_u64, 18, 30]));
+        assert_eq!(Some(1), lcm_all(Vec::<u64>::new()));
+        assert_eq!(Some(0), lcm_all([4_u64, 0]));
+        assert_eq!(Some(12), checked_lcm(&-4_i64, &6));
+        assert_eq!(None, lcm_all([u64::MAX, u64::MAX - 1]));
+    }
+
+    #[test]
+    fn verify_crt() {
+        // Then
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]).unwrap());
+        assert_eq!(Some((10, 12)), crt([(2, 4), (4, 6)]).unwrap());
+        assert_eq!(None, crt([(1, 4), (2, 6)]).unwrap());
+        assert_eq!(Some((0, 1)), crt([]).unwrap());
+        assert_eq!(
+            "Invalid input: Modulus 0 is not positive",
+            crt([(1, 0)]).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Overflow: Combined modulus 85070591730234615838173535747377725442 does not fit in i64",
+            crt([(0, i64::MAX), (1, i64::MAX - 1)])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn verify_primes() {
+        // Then
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19], primes_up_to(20));
+        assert!(primes_up_to(1).is_empty());
+        assert_eq!(vec![(2, 3), (3, 2), (5, 1)], factorize(360));
+        assert_eq!(vec![(1_000_000_007, 1)], factorize(1_000_000_007));
+        assert_eq!(vec![(65_537, 1), (65_539, 1)], factorize(65_537 * 65_539));
+        assert!(factorize(1).is_empty());
+        assert_eq!(Some(4), mod_inverse(3_i64, 11));
+        assert_eq!(None, mod_inverse(4_i64, 10));
+        assert_eq!(None, mod_inverse(3_i64, 0));
+        assert_eq!(None, mod_inverse(3_i64, -11));
+        assert_eq!(4_294_967_295, isqrt(u64::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_lcm_properties(a in 1..1_000_000_u64, b in 1..1_000_000_u64) {
+            let gcd = gcd_all([a, b]);
+            let lcm = lcm_all([a, b]).unwrap();
+
+            prop_assert_eq!(0, a % gcd);
+            prop_assert_eq!(0, b % gcd);
+            prop_assert_eq!(0, lcm % a);
+            prop_assert_eq!(0, lcm % b);
+            prop_assert_eq!(a * b, gcd * lcm);
+        }
+
+        #[test]
+        fn extended_gcd_properties(a in -1_000_000..1_000_000_i64, b in -1_000_000..1_000_000_i64) {
+            let (gcd, x, y) = extended_gcd(a, b);
+
+            prop_assert_eq!(gcd, gcd_all([a, b]));
+            prop_assert_eq!(gcd, a * x + b * y);
+        }
+
+        #[test]
+        fn mod_inverse_properties(a in -1_000_000..1_000_000_i64, modulus in 2..1_000_000_i64) {
+            match mod_inverse(a, modulus) {
+                Some(inverse) => {
+                    prop_assert!((0..modulus).contains(&inverse));
+                    prop_assert_eq!(1, (a * inverse).mod_floor(&modulus));
+                }
+                None => prop_assert!(gcd_all([a, modulus]) > 1),
+            }
+        }
+
+        #[test]
+        fn crt_finds_smallest_solution(
+            x in 0..1_000_000_000_i64,
+            moduli in proptest::collection::vec(1..1_000_i64, 1..5),
+        ) {
+            let congruences: Vec<(i64, i64)> =
+                moduli.iter().map(|modulus| (x % modulus, *modulus)).collect();
+
+            let (solution, combined) = crt(congruences).unwrap().unwrap();
+
+            prop_assert_eq!(Some(combined), lcm_all(moduli.iter().copied()));
+            prop_assert_eq!(x % combined, solution);
+        }
+
+        #[test]
+        fn isqrt_properties(n in any::<u64>()) {
+            let root = u128::from(isqrt(n));
+
+            prop_assert!(root * root <= u128::from(n));
+            prop_assert!((root + 1) * (root + 1) > u128::from(n));
+        }
+
+        #[test]
+        fn factorize_properties(n in 1..10_000_000_u64) {
+            let factors = factorize(n);
+
+            prop_assert_eq!(n, factors.iter().map(|(p, power)| p.pow(*power)).product::<u64>());
+            prop_assert!(factors.iter().all(|(p, _)| is_prime(*p)));
+            prop_assert!(factors.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+
+        #[test]
+        fn sieve_matches_is_prime(limit in 0..2_000_usize) {
+            let expected: Vec<u64> = (0..=limit as u64).filter(|n| is_prime(*n)).collect();
+
+            prop_assert_eq!(expected, primes_up_to(limit));
+        }
+    }
+}
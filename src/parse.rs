@@ -17,3 +17,14 @@ pub fn parse_num_line_with_prefix<T: Iterator<Item = Result<String, std::io::Err
         .map(|it| it.parse())
         .collect::<Result<Vec<u64>, _>>()?)
 }
+
+pub fn find_option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| value.as_str())
+}
+
+pub fn has_flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|arg| arg == name)
+}